@@ -0,0 +1,73 @@
+//! Scopeguard-style cleanup/rollback registry.
+//!
+//! Simulations that mutate host state (Defender exclusions, scheduled tasks,
+//! registry keys, ...) register an undo closure with a `CleanupGuard` as soon
+//! as the mutation succeeds. The guard runs every registered closure when it
+//! drops — including on panic or an early `?` return — so a simulation can
+//! never leak state just because it didn't reach its own cleanup line.
+
+use crate::core::config::Config;
+use crate::core::logger;
+use crate::core::telemetry::{write_action_record, ActionRecord};
+use chrono::Utc;
+
+type Undo = Box<dyn FnOnce() -> anyhow::Result<()> + Send>;
+
+/// Holds pending undo actions for one `Simulation::run` invocation.
+///
+/// Construct one at the top of `run`, `push` an undo closure immediately
+/// after each state-mutating step succeeds, and let it fall out of scope at
+/// the end of `run`. If `Config::keep_artifacts` is set, the guard skips
+/// reverting so the artifacts are left in place for inspection.
+pub struct CleanupGuard<'a> {
+    sim_name: &'static str,
+    cfg: &'a Config,
+    undos: Vec<(String, Undo)>,
+}
+
+impl<'a> CleanupGuard<'a> {
+    pub fn new(sim_name: &'static str, cfg: &'a Config) -> Self {
+        Self { sim_name, cfg, undos: Vec::new() }
+    }
+
+    /// Register an undo closure, described by `label` for telemetry/logging.
+    /// Closures run in reverse registration order (last mutation undone first).
+    pub fn push(&mut self, label: impl Into<String>, undo: impl FnOnce() -> anyhow::Result<()> + Send + 'static) {
+        self.undos.push((label.into(), Box::new(undo)));
+    }
+}
+
+impl<'a> Drop for CleanupGuard<'a> {
+    fn drop(&mut self) {
+        if self.cfg.keep_artifacts {
+            if !self.undos.is_empty() {
+                logger::info(&format!(
+                    "{}: keep_artifacts set, leaving {} change(s) in place",
+                    self.sim_name,
+                    self.undos.len()
+                ));
+            }
+            return;
+        }
+
+        while let Some((label, undo)) = self.undos.pop() {
+            let status = match undo() {
+                Ok(()) => "reverted",
+                Err(e) => {
+                    logger::warn(&format!("{}: cleanup '{}' failed: {}", self.sim_name, label, e));
+                    "revert_failed"
+                }
+            };
+
+            let rec = ActionRecord {
+                test_id: self.cfg.test_id.clone(),
+                timestamp: Utc::now().to_rfc3339(),
+                action: format!("{}::cleanup", self.sim_name),
+                status: status.into(),
+                details: label,
+                artifact_path: None,
+            };
+            let _ = write_action_record(self.cfg, &rec);
+        }
+    }
+}
@@ -1,33 +1,93 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::PathBuf;
 
 /// Lightweight config used across simulations.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// If true, do not perform filesystem writes; just print what would happen.
+    #[serde(default)]
     pub dry_run: bool,
 
     /// A test ID stamped into produced artifacts to aid SOC correlation.
+    /// Defaults to a fresh timestamp-derived ID whenever it's absent from the
+    /// config file, so a base config layer that doesn't pin one down still
+    /// gets a unique ID per run instead of every run reusing the same one.
+    #[serde(default = "default_test_id")]
     pub test_id: String,
+
+    /// If true, skip the automatic rollback simulations register via
+    /// `core::cleanup::CleanupGuard` and leave artifacts (exclusions,
+    /// scheduled tasks, ...) in place for inspection.
+    #[serde(default)]
+    pub keep_artifacts: bool,
+
+    /// Path to a malleable beacon profile (TOML) consumed by
+    /// `windows::http_traffic_sim`. `None` runs that simulation's built-in
+    /// fixed-cadence, single-target defaults.
+    #[serde(default)]
+    pub beacon_profile: Option<String>,
+
+    /// Collector URL that the end-of-run `core::run_report::RunReport` is
+    /// POSTed to, in addition to being written to the telemetry directory.
+    /// `None` skips the upload.
+    #[serde(default)]
+    pub report_endpoint: Option<String>,
+
+    /// Telemetry output directory, honored by `core::run_report::RunReport`.
+    /// `None` falls back to `core::telemetry::telemetry_dir()`'s default
+    /// (`Documents\MagnetTelemetry` under the user's home directory).
+    #[serde(default)]
+    pub telemetry_dir: Option<String>,
+}
+
+fn default_test_id() -> String {
+    format!("MAGNET-TEST-{}", Utc::now().format("%Y%m%d%H%M%S"))
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             dry_run: false,
-            test_id: format!("MAGNET-TEST-{}", Utc::now().format("%Y%m%d%H%M%S")),
+            test_id: default_test_id(),
+            keep_artifacts: false,
+            beacon_profile: None,
+            report_endpoint: None,
+            telemetry_dir: None,
         }
     }
 }
 
 impl Config {
-    /// Loads config from environment variables if present.
+    /// Standard config file path (`<config_dir>/magnet/config.toml`), written
+    /// by `magnet init` and read as this config's base layer before env vars
+    /// are applied on top.
+    pub fn config_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|mut p| {
+            p.push("magnet");
+            p.push("config.toml");
+            p
+        })
+    }
+
+    /// Loads config from the standard config file (if present), then applies
+    /// environment variables on top of it:
     /// - MAGNET_DRY_RUN = "1" enables dry-run
     /// - MAGNET_TEST_ID = custom ID
+    /// - MAGNET_KEEP_ARTIFACTS = "1" disables automatic rollback
+    /// - MAGNET_BEACON_PROFILE = path to a beacon profile TOML file
+    /// - MAGNET_REPORT_ENDPOINT = collector URL to POST the run report to
+    /// - MAGNET_TELEMETRY_DIR = telemetry output directory
     pub fn load() -> Result<Self> {
-        let mut cfg = Config::default();
+        let mut cfg = Self::load_from_file().unwrap_or_else(|e| {
+            crate::core::logger::warn(&format!(
+                "ignoring config file, falling back to defaults: {}",
+                e
+            ));
+            Self::default()
+        });
 
         if let Ok(v) = env::var("MAGNET_DRY_RUN") {
             if v == "1" || v.eq_ignore_ascii_case("true") {
@@ -41,6 +101,41 @@ impl Config {
             }
         }
 
+        if let Ok(v) = env::var("MAGNET_KEEP_ARTIFACTS") {
+            if v == "1" || v.eq_ignore_ascii_case("true") {
+                cfg.keep_artifacts = true;
+            }
+        }
+
+        if let Ok(path) = env::var("MAGNET_BEACON_PROFILE") {
+            if !path.trim().is_empty() {
+                cfg.beacon_profile = Some(path);
+            }
+        }
+
+        if let Ok(url) = env::var("MAGNET_REPORT_ENDPOINT") {
+            if !url.trim().is_empty() {
+                cfg.report_endpoint = Some(url);
+            }
+        }
+
+        if let Ok(dir) = env::var("MAGNET_TELEMETRY_DIR") {
+            if !dir.trim().is_empty() {
+                cfg.telemetry_dir = Some(dir);
+            }
+        }
+
         Ok(cfg)
     }
+
+    /// Read `config_path()` as the base layer, falling back to `Default`
+    /// when it doesn't exist yet (new users who haven't run `magnet init`).
+    fn load_from_file() -> Result<Self> {
+        let path = Self::config_path().ok_or_else(|| anyhow::anyhow!("could not determine config directory"))?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path).with_context(|| format!("reading config file {}", path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("parsing config file {}", path.display()))
+    }
 }
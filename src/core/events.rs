@@ -0,0 +1,37 @@
+//! Machine-readable run events.
+//!
+//! `core::logger` is colorized prose meant for a human at a terminal.
+//! External orchestrators and detection-validation harnesses need something
+//! they can parse line-by-line instead, so `run_selected` can also emit one
+//! `RunEvent` per lifecycle moment as a line of JSON (NDJSON) on stdout,
+//! letting a SIEM-correlation script line up each run with the module and
+//! timing that produced it.
+
+use serde::Serialize;
+
+/// One lifecycle event from a run loop, serialized as a single JSON line.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RunEvent {
+    /// Emitted once, before any module runs.
+    Plan { modules: Vec<String>, total: usize },
+    /// Emitted just before a module starts.
+    ModuleStart { name: String },
+    /// Emitted after a module finishes.
+    ModuleResult {
+        name: String,
+        status: String,
+        duration_ms: u128,
+        error: Option<String>,
+    },
+}
+
+impl RunEvent {
+    /// Print this event as a single JSON line on stdout.
+    pub fn emit(&self) {
+        match serde_json::to_string(self) {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("failed to serialize run event: {}", e),
+        }
+    }
+}
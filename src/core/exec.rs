@@ -0,0 +1,178 @@
+//! Unified process-spawn abstraction.
+//!
+//! The handful of simulations that shell out (`wmic`, `powershell.exe`,
+//! `schtasks.exe`) used to build a bare `std::process::Command` with no
+//! grouping, timeout, or reliable kill path, so a hung child (e.g.
+//! `powershell.exe` stalled on a Defender query) would just sit there.
+//! `Program` + `SpawnOptions` centralize how a command is built, and
+//! `run_with_timeout` bounds execution and terminates the whole process
+//! group on timeout instead of orphaning children.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How to invoke a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    /// Run the executable directly, no shell involved.
+    Raw,
+    /// Run through `cmd.exe /C`.
+    Cmd,
+    /// Run through `powershell.exe -Command`.
+    PowerShell,
+}
+
+/// A program to run, plus how it should be launched.
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub shell: Shell,
+    pub executable: String,
+    pub args: Vec<String>,
+}
+
+impl Program {
+    /// Run `executable` directly with `args`, no shell involved.
+    pub fn raw(executable: impl Into<String>, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self { shell: Shell::Raw, executable: executable.into(), args: args.into_iter().map(Into::into).collect() }
+    }
+
+    /// Run `command` through a non-interactive `powershell.exe`.
+    pub fn powershell(command: impl Into<String>) -> Self {
+        Self {
+            shell: Shell::PowerShell,
+            executable: "powershell.exe".into(),
+            args: vec![
+                "-NoProfile".into(),
+                "-NonInteractive".into(),
+                "-ExecutionPolicy".into(),
+                "Bypass".into(),
+                "-Command".into(),
+                command.into(),
+            ],
+        }
+    }
+
+    fn to_command(&self) -> Command {
+        let mut cmd = Command::new(&self.executable);
+        cmd.args(&self.args);
+        cmd
+    }
+}
+
+/// How to spawn a `Program`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpawnOptions {
+    pub capture_stdout: bool,
+    pub capture_stderr: bool,
+    /// Put the child in its own process group so a timeout kills the whole
+    /// subtree instead of just the direct child.
+    pub process_group: bool,
+}
+
+/// Why a spawned process stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessEnd {
+    Exited(i32),
+    Signalled,
+    TimedOut,
+}
+
+/// Result of running a `Program` to completion (or timeout).
+#[derive(Debug, Default)]
+pub struct ExecOutput {
+    pub end: Option<ProcessEnd>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ExecOutput {
+    pub fn success(&self) -> bool {
+        matches!(self.end, Some(ProcessEnd::Exited(0)))
+    }
+}
+
+/// Spawn `program` and poll it to completion, killing its process group (if
+/// `opts.process_group` is set) once `timeout` elapses rather than leaving it
+/// to run unbounded.
+pub fn run_with_timeout(program: &Program, opts: &SpawnOptions, timeout: Duration) -> Result<ExecOutput> {
+    let mut cmd = program.to_command();
+    if opts.capture_stdout {
+        cmd.stdout(Stdio::piped());
+    }
+    if opts.capture_stderr {
+        cmd.stderr(Stdio::piped());
+    }
+
+    #[cfg(windows)]
+    if opts.process_group {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        cmd.creation_flags(CREATE_NEW_PROCESS_GROUP);
+    }
+
+    let start = Instant::now();
+    let mut child = cmd.spawn().with_context(|| format!("failed to spawn {}", program.executable))?;
+
+    // Drain stdout/stderr on their own threads, concurrently with the wait
+    // loop below, rather than only after the child exits or times out — a
+    // chatty child (e.g. `wmic process get name`, a verbose Defender dump)
+    // can fill the OS pipe buffer and block on write long before it exits,
+    // and polling `try_wait()` alone would never notice: it just deadlocks
+    // until the timeout fires instead of bounding on the actual output.
+    let stdout_reader = child.stdout.take().map(spawn_pipe_reader);
+    let stderr_reader = child.stderr.take().map(spawn_pipe_reader);
+
+    let end = loop {
+        if let Some(status) = child.try_wait().context("polling child status")? {
+            break match status.code() {
+                Some(code) => ProcessEnd::Exited(code),
+                None => ProcessEnd::Signalled,
+            };
+        }
+
+        if start.elapsed() >= timeout {
+            kill_process_group(&mut child);
+            let _ = child.wait();
+            break ProcessEnd::TimedOut;
+        }
+
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    // The child (or its process group) is gone by now, so its pipes have
+    // closed and these joins return promptly with whatever was read.
+    let stdout = join_pipe_reader(stdout_reader);
+    let stderr = join_pipe_reader(stderr_reader);
+
+    Ok(ExecOutput { end: Some(end), stdout, stderr })
+}
+
+/// Spawn a thread that reads `pipe` to EOF into a `String`, so a full OS
+/// pipe buffer never blocks the caller's own wait loop.
+fn spawn_pipe_reader(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = pipe.read_to_string(&mut buf);
+        buf
+    })
+}
+
+fn join_pipe_reader(reader: Option<std::thread::JoinHandle<String>>) -> String {
+    reader.and_then(|h| h.join().ok()).unwrap_or_default()
+}
+
+/// Terminate `child`'s whole process group. On Windows this shells out to
+/// `taskkill /T /F`, which reliably tears down a subtree; on other platforms
+/// a direct kill is enough given these simulations spawn no further children.
+fn kill_process_group(child: &mut Child) {
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill").args(["/PID", &child.id().to_string(), "/T", "/F"]).output();
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = child.kill();
+    }
+}
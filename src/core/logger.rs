@@ -1,10 +1,13 @@
 use colored::*;
 use std::time::Duration;
 
-/// Initialize logger (enable ANSI on Windows admin shells)
+/// Initialize logger: enable ANSI on Windows admin shells and install the
+/// `tracing` subscriber that `core::telemetry` reads simulation output through.
 pub fn init() {
     #[cfg(windows)]
     enable_ansi_colors();
+
+    crate::core::telemetry::init_subscriber();
 }
 
 #[cfg(windows)]
@@ -62,23 +65,27 @@ pub fn action_ok() {
 pub fn action_fail(msg: &str) {
     let fail = " ❌".bright_red().bold();
     println!("   {} {}", fail, msg.bright_red());
+    tracing::warn!(message = %msg);
 }
 
 /// Print an info line
 pub fn info(msg: &str) {
     println!("   {}", msg.dimmed());
+    tracing::info!(message = %msg);
 }
 
 /// Print a warning
 pub fn warn(msg: &str) {
     let w = "⚠".yellow();
     println!("{} {}", w, msg.yellow());
+    tracing::warn!(message = %msg);
 }
 
 /// Print an error
 pub fn error(msg: &str) {
     let e = "✖".red();
     println!("{} {}", e, msg.red().bold());
+    tracing::error!(message = %msg);
 }
 
 /// Final summary footer
@@ -0,0 +1,170 @@
+//! Enumerable registry over every simulation.
+//!
+//! Each action module registers itself with `main.rs` by hand today, and
+//! `Simulation::techniques` (added alongside this module) only tells you
+//! what one instance covers. `SimId` is modeled on a strum `EnumIter` — we
+//! don't take the `strum` dependency since the variant list already has to
+//! be kept in sync with `register_windows_actions!` by hand, so `ALL`/`iter`
+//! are just a const slice — giving a single place to list every known sim,
+//! filter by ATT&CK technique or tactic, and build an ordered chain of
+//! `Box<dyn Simulation>` from the result.
+
+use crate::core::simulation::{Simulation, Technique};
+use crate::platforms::windows::actions::{
+    add_admin_user::AdminUserAddSimulation,
+    browser_pwd::BrowserPwdSimulation,
+    cred_manager_access::CredManagerSimulation,
+    directory_permissions::DirectoryPermissionsSim,
+    discovery_sim::DiscoverySim,
+    dll_load_storm::DllLoadStormSimulation,
+    edr_discovery::EdrDiscoverySimulation,
+    enable_winrm::EnableWinRMSimulation,
+    high_cpu_miner_sim::HighCpuMinerSimulation,
+    http_traffic_sim::HttpTrafficSimulation,
+    install_python::InstallPythonSimulation,
+    keylogger_sim::KeyloggerSim,
+    network_port_scan::NetworkPortScanSimulation,
+    open_many_windows::OpenManyWindowsSimulation,
+    proc_inj::ProcInjSim,
+    ps_defender_exclusions::PsDefenderExclusions,
+    ps_elev_whoami::PsElevWhoami,
+    ransom_note::RansomNote,
+    ransomware_sim::RansomSimulation,
+    record_mic::RecordMicSim,
+    rev_sh::RevSh,
+    scheduled_task_sim::ScheduledTaskSim,
+    screenshot_sim::ScreenshotSimulation,
+    wifi_creds::WifiCreds,
+};
+
+/// One variant per simulation module under `platforms::windows::actions`.
+/// Keep this list (and `ALL`) in sync with that directory — note that as of
+/// this writing `main.rs`'s own `register_windows_actions!` call list has
+/// already drifted from it (it references modules with no corresponding
+/// file, like `pwd_guessing`/`enable_rdp`), so `main.rs` is not a reliable
+/// source of truth to sync against either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimId {
+    AdminUserAdd,
+    BrowserPwd,
+    CredManagerAccess,
+    DirectoryPermissions,
+    Discovery,
+    DllLoadStorm,
+    EdrDiscovery,
+    EnableWinRM,
+    HighCpuMiner,
+    HttpTraffic,
+    InstallPython,
+    Keylogger,
+    NetworkPortScan,
+    OpenManyWindows,
+    ProcInj,
+    PsDefenderExclusions,
+    PsElevWhoami,
+    RansomNote,
+    Ransomware,
+    RecordMic,
+    RevSh,
+    ScheduledTask,
+    Screenshot,
+    WifiCreds,
+}
+
+impl SimId {
+    /// Every known simulation, in registration order.
+    pub const ALL: &'static [SimId] = &[
+        SimId::AdminUserAdd,
+        SimId::BrowserPwd,
+        SimId::CredManagerAccess,
+        SimId::DirectoryPermissions,
+        SimId::Discovery,
+        SimId::DllLoadStorm,
+        SimId::EdrDiscovery,
+        SimId::EnableWinRM,
+        SimId::HighCpuMiner,
+        SimId::HttpTraffic,
+        SimId::InstallPython,
+        SimId::Keylogger,
+        SimId::NetworkPortScan,
+        SimId::OpenManyWindows,
+        SimId::ProcInj,
+        SimId::PsDefenderExclusions,
+        SimId::PsElevWhoami,
+        SimId::RansomNote,
+        SimId::Ransomware,
+        SimId::RecordMic,
+        SimId::RevSh,
+        SimId::ScheduledTask,
+        SimId::Screenshot,
+        SimId::WifiCreds,
+    ];
+
+    /// Iterate every known simulation, in registration order.
+    pub fn iter() -> impl Iterator<Item = SimId> {
+        Self::ALL.iter().copied()
+    }
+
+    /// Instantiate the simulation this id refers to.
+    pub fn build(self) -> Box<dyn Simulation> {
+        match self {
+            SimId::AdminUserAdd => Box::new(AdminUserAddSimulation::default()),
+            SimId::BrowserPwd => Box::new(BrowserPwdSimulation::default()),
+            SimId::CredManagerAccess => Box::new(CredManagerSimulation::default()),
+            SimId::DirectoryPermissions => Box::new(DirectoryPermissionsSim::default()),
+            SimId::Discovery => Box::new(DiscoverySim::default()),
+            SimId::DllLoadStorm => Box::new(DllLoadStormSimulation::default()),
+            SimId::EdrDiscovery => Box::new(EdrDiscoverySimulation::default()),
+            SimId::EnableWinRM => Box::new(EnableWinRMSimulation::default()),
+            SimId::HighCpuMiner => Box::new(HighCpuMinerSimulation::default()),
+            SimId::HttpTraffic => Box::new(HttpTrafficSimulation::default()),
+            SimId::InstallPython => Box::new(InstallPythonSimulation::default()),
+            SimId::Keylogger => Box::new(KeyloggerSim::default()),
+            SimId::NetworkPortScan => Box::new(NetworkPortScanSimulation::default()),
+            SimId::OpenManyWindows => Box::new(OpenManyWindowsSimulation::default()),
+            SimId::ProcInj => Box::new(ProcInjSim::default()),
+            SimId::PsDefenderExclusions => Box::new(PsDefenderExclusions::default()),
+            SimId::PsElevWhoami => Box::new(PsElevWhoami::default()),
+            SimId::RansomNote => Box::new(RansomNote::default()),
+            SimId::Ransomware => Box::new(RansomSimulation::default()),
+            SimId::RecordMic => Box::new(RecordMicSim::default()),
+            SimId::RevSh => Box::new(RevSh::default()),
+            SimId::ScheduledTask => Box::new(ScheduledTaskSim::default()),
+            SimId::Screenshot => Box::new(ScreenshotSimulation::default()),
+            SimId::WifiCreds => Box::new(WifiCreds::default()),
+        }
+    }
+
+    /// Full module name (e.g. `windows::edr_discovery`), as returned by
+    /// `Simulation::name`. Derived from `build()` rather than hand-duplicating
+    /// the variant match a second time — one extra `Box` per lookup is a
+    /// fine trade against a second 24-arm match silently drifting from the
+    /// one in `build()` whenever a sim is added or removed.
+    pub fn full_name(self) -> &'static str {
+        self.build().name()
+    }
+
+    /// ATT&CK techniques this simulation exercises. Derived from `build()`
+    /// for the same reason as `full_name`.
+    pub fn techniques(self) -> &'static [Technique] {
+        self.build().techniques()
+    }
+}
+
+/// Every sim whose `techniques()` includes a technique with this id
+/// (e.g. `"T1082"`), in registration order.
+pub fn by_technique(id: &str) -> Vec<SimId> {
+    SimId::iter().filter(|s| s.techniques().iter().any(|t| t.id == id)).collect()
+}
+
+/// Every sim whose `techniques()` includes a technique under this tactic
+/// (e.g. `"Discovery"`), in registration order.
+pub fn by_tactic(tactic: &str) -> Vec<SimId> {
+    SimId::iter().filter(|s| s.techniques().iter().any(|t| t.tactic.eq_ignore_ascii_case(tactic))).collect()
+}
+
+/// Build `ids` into simulation instances, preserving order, for the caller
+/// to feed into a `Runner` as a chained attack sequence.
+pub fn chain(ids: &[SimId]) -> Vec<Box<dyn Simulation>> {
+    ids.iter().map(|id| id.build()).collect()
+}
@@ -0,0 +1,115 @@
+//! Aggregated run report.
+//!
+//! `main` used to always return `Ok(())` regardless of whether any module
+//! failed, and the only record of a run's outcome was colorized prose (or,
+//! under `--output json`, the `RunEvent` stream) scrolling past on stdout —
+//! nothing a CI job or scheduled validation pipeline could gate on. After
+//! `run_selected` finishes, `main` builds a [`RunReport`] from its per-module
+//! results, writes it to the telemetry directory as `run_report_<test_id>.json`,
+//! optionally POSTs it to `Config::report_endpoint` (mirroring how benchmark
+//! harnesses upload workload results to a central collector), and uses
+//! [`RunReport::any_failed`] to decide the process exit code.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::time::Duration;
+
+use crate::core::config::Config;
+use crate::core::telemetry;
+
+/// Outcome of a single module within a run, as reported by `execute_module`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleReportEntry {
+    pub name: String,
+    pub status: String,
+    pub duration_ms: u128,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub run_id: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub host_os: String,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Modules that ran as `dry_run: true` (status "dry-run" from
+    /// `execute_module`) — counted separately from `succeeded` so a dry run
+    /// can't be mistaken for a real passing validation run.
+    pub dry_run: usize,
+    pub modules: Vec<ModuleReportEntry>,
+}
+
+impl RunReport {
+    pub fn build(cfg: &Config, started_at: DateTime<Utc>, modules: Vec<ModuleReportEntry>) -> Self {
+        let total = modules.len();
+        let failed = modules.iter().filter(|m| m.status == "failed").count();
+        let dry_run = modules.iter().filter(|m| m.status == "dry-run").count();
+        let succeeded = modules.iter().filter(|m| m.status == "ok").count();
+
+        Self {
+            run_id: cfg.test_id.clone(),
+            started_at,
+            ended_at: Utc::now(),
+            host_os: std::env::consts::OS.to_string(),
+            total,
+            succeeded,
+            failed,
+            dry_run,
+            modules,
+        }
+    }
+
+    pub fn any_failed(&self) -> bool {
+        self.failed > 0
+    }
+
+    /// Write as `run_report_<test_id>.json` in the telemetry directory —
+    /// `cfg.telemetry_dir` if set (via `magnet init` or `MAGNET_TELEMETRY_DIR`),
+    /// otherwise `telemetry::telemetry_dir()`'s default.
+    pub fn write(&self, cfg: &Config) -> Result<()> {
+        let dir = cfg
+            .telemetry_dir
+            .as_ref()
+            .map(std::path::PathBuf::from)
+            .or_else(telemetry::telemetry_dir)
+            .ok_or_else(|| anyhow::anyhow!("could not determine telemetry output path"))?;
+        fs::create_dir_all(&dir).with_context(|| format!("creating telemetry directory {}", dir.display()))?;
+
+        let mut path = dir;
+        path.push(format!("run_report_{}.json", cfg.test_id));
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json).with_context(|| format!("writing run report to {}", path.display()))?;
+        Ok(())
+    }
+
+    /// POST this report to `cfg.report_endpoint`, reusing a plain blocking
+    /// `reqwest` client the same way `http_traffic_sim` does. No-op when no
+    /// endpoint is configured.
+    pub fn upload(&self, cfg: &Config) -> Result<()> {
+        let Some(endpoint) = &cfg.report_endpoint else {
+            return Ok(());
+        };
+
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("failed to build HTTP client")?;
+
+        let res = client
+            .post(endpoint)
+            .json(self)
+            .send()
+            .with_context(|| format!("failed to POST run report to {}", endpoint))?;
+
+        if !res.status().is_success() {
+            anyhow::bail!("run report upload to {} returned status {}", endpoint, res.status());
+        }
+
+        Ok(())
+    }
+}
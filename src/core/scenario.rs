@@ -0,0 +1,97 @@
+//! Scenario files: an ordered, version-controllable sequence of steps.
+//!
+//! `magnet run windows all` only understands a flat module list under one OS
+//! namespace, executed in registration order. A scenario describes a
+//! cross-OS attack chain (discovery → credential access → persistence, say)
+//! as JSON or YAML instead of a long ad-hoc command line: each step names an
+//! OS namespace, a module, optional `Config` overrides, an optional delay
+//! before the next step, and what to do if the step itself fails.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::core::config::Config;
+
+/// What to do when a step's simulation returns `Err`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnError {
+    /// Stop the scenario; later steps don't run.
+    Abort,
+    /// Log the failure and move on to the next step.
+    Continue,
+}
+
+impl Default for OnError {
+    fn default() -> Self {
+        OnError::Abort
+    }
+}
+
+/// Per-step overrides applied on top of the scenario runner's base `Config`.
+/// Every field is optional; unset fields keep the base value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigOverride {
+    pub dry_run: Option<bool>,
+    pub test_id: Option<String>,
+    pub keep_artifacts: Option<bool>,
+}
+
+impl ConfigOverride {
+    /// Clone `base` and apply whichever fields this override sets.
+    pub fn apply(&self, base: &Config) -> Config {
+        let mut cfg = base.clone();
+        if let Some(dry_run) = self.dry_run {
+            cfg.dry_run = dry_run;
+        }
+        if let Some(test_id) = &self.test_id {
+            cfg.test_id = test_id.clone();
+        }
+        if let Some(keep_artifacts) = self.keep_artifacts {
+            cfg.keep_artifacts = keep_artifacts;
+        }
+        cfg
+    }
+}
+
+/// One step of a scenario: run `module` under `os`, waiting `delay_ms`
+/// before the next step starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioStep {
+    /// OS namespace to run under (e.g. `windows`), matched against
+    /// `collect_modules_by_os` the same way `run_selected` does.
+    pub os: String,
+    /// Module short name (`edr_discovery`) or full name (`windows::edr_discovery`).
+    pub module: String,
+    /// Overrides applied to the base `Config` for just this step.
+    #[serde(default)]
+    pub config: ConfigOverride,
+    /// Milliseconds to wait after this step completes before starting the next.
+    pub delay_ms: Option<u64>,
+    /// What to do if this step's simulation fails or its module can't be resolved.
+    #[serde(default)]
+    pub on_error: OnError,
+}
+
+/// A named, ordered attack chain loaded from a scenario file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub steps: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    /// Load a scenario from `path`, parsing as YAML for a `.yaml`/`.yml`
+    /// extension and JSON otherwise.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path).with_context(|| format!("reading scenario file {}", path.display()))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => {
+                serde_yaml::from_str(&raw).with_context(|| format!("parsing scenario {} as YAML", path.display()))
+            }
+            _ => serde_json::from_str(&raw).with_context(|| format!("parsing scenario {} as JSON", path.display())),
+        }
+    }
+}
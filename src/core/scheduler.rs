@@ -0,0 +1,142 @@
+//! Time-window simulation scheduler.
+//!
+//! Modeled on a release-time scheduler: simulations are queued against an
+//! absolute release time rather than each one blocking in place (the way
+//! `ScheduledTaskSim` used to hardcode a `sleep(61s)`). The schedule persists
+//! to disk as JSON so a multi-stage emulation plan survives a restart, and
+//! can be queried/edited over a time window instead of by exact key.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One simulation queued to run at a specific time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledSim {
+    /// Full module name, e.g. `windows::scheduled_task_sim`.
+    pub sim_name: String,
+    /// test_id to stamp into the run's telemetry.
+    pub test_id: String,
+    /// Stable id for this entry (`<test_id>-<sequence>`), so a CLI or log
+    /// line can refer to one queued run unambiguously.
+    pub request_id: String,
+}
+
+/// A bound used by `delete_within`/`list_within` to select queued entries by
+/// release time instead of having to know the exact key.
+#[derive(Debug, Clone, Copy)]
+pub enum TimeWindow {
+    SelectBetween(DateTime<Utc>, DateTime<Utc>),
+    FromStartUntil(DateTime<Utc>),
+    FromTimeToEnd(DateTime<Utc>),
+}
+
+impl TimeWindow {
+    fn contains(&self, t: DateTime<Utc>) -> bool {
+        match self {
+            TimeWindow::SelectBetween(start, end) => t >= *start && t <= *end,
+            TimeWindow::FromStartUntil(end) => t <= *end,
+            TimeWindow::FromTimeToEnd(start) => t >= *start,
+        }
+    }
+}
+
+/// Pending simulations keyed by release time.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Scheduler {
+    pending: BTreeMap<DateTime<Utc>, Vec<ScheduledSim>>,
+    /// Persisted (not `#[serde(skip)]`) so `request_id`s stay unique across
+    /// `load`/`save` round trips — `magnet schedule enqueue` does exactly one
+    /// of those per CLI invocation, so a skipped counter would reset to 0 on
+    /// every call and hand out the same `request_id` to every first entry
+    /// queued under a given `test_id`.
+    #[serde(default)]
+    sequence: u64,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default on-disk location for the schedule, alongside telemetry output.
+    pub fn default_path() -> Option<PathBuf> {
+        crate::core::telemetry::telemetry_dir().map(|mut p| {
+            p.push("schedule.json");
+            p
+        })
+    }
+
+    /// Load a previously persisted schedule, or an empty one if none exists yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let raw = fs::read_to_string(path).with_context(|| format!("reading schedule {}", path.display()))?;
+        let sched: Scheduler =
+            serde_json::from_str(&raw).with_context(|| format!("parsing schedule {}", path.display()))?;
+        Ok(sched)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let raw = serde_json::to_string_pretty(self).context("serializing schedule")?;
+        fs::write(path, raw).with_context(|| format!("writing schedule {}", path.display()))
+    }
+
+    /// Queue `sim_name` to run at `time`, returning the entry's stable request id.
+    pub fn insert_at(&mut self, time: DateTime<Utc>, sim_name: impl Into<String>, test_id: impl Into<String>) -> String {
+        self.sequence += 1;
+        let test_id = test_id.into();
+        let request_id = format!("{}-{}", test_id, self.sequence);
+        self.pending.entry(time).or_default().push(ScheduledSim {
+            sim_name: sim_name.into(),
+            test_id,
+            request_id: request_id.clone(),
+        });
+        request_id
+    }
+
+    /// Pop and return every entry whose release time is `<= now`, ordered by
+    /// release time (earliest first).
+    pub fn release_due(&mut self, now: DateTime<Utc>) -> Vec<ScheduledSim> {
+        let due_keys: Vec<DateTime<Utc>> = self.pending.range(..=now).map(|(k, _)| *k).collect();
+        let mut out = Vec::new();
+        for key in due_keys {
+            if let Some(entries) = self.pending.remove(&key) {
+                out.extend(entries);
+            }
+        }
+        out
+    }
+
+    /// Remove and return every entry whose release time falls in `window`.
+    pub fn delete_within(&mut self, window: TimeWindow) -> Vec<ScheduledSim> {
+        let keys: Vec<DateTime<Utc>> = self.pending.keys().copied().filter(|k| window.contains(*k)).collect();
+        let mut out = Vec::new();
+        for key in keys {
+            if let Some(entries) = self.pending.remove(&key) {
+                out.extend(entries);
+            }
+        }
+        out
+    }
+
+    /// List (without removing) every entry whose release time falls in `window`.
+    pub fn list_within(&self, window: TimeWindow) -> Vec<(DateTime<Utc>, &ScheduledSim)> {
+        self.pending
+            .iter()
+            .filter(|(k, _)| window.contains(**k))
+            .flat_map(|(k, v)| v.iter().map(move |s| (*k, s)))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
@@ -1,4 +1,17 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// A single MITRE ATT&CK technique reference, structured instead of the
+/// free-text `"T1082 - T1518 - ..."` strings that used to get embedded
+/// directly in `ActionRecord.action`. The `Deserialize` side lets
+/// `telemetry::MagnetTelemetryLayer` round-trip a sim's techniques through
+/// a `tracing` event field and back into an `ActionRecord`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Technique {
+    pub id: &'static str,
+    pub tactic: &'static str,
+    pub name: &'static str,
+}
 
 /// Common trait every action/simulation must implement.
 pub trait Simulation: Send + Sync {
@@ -7,4 +20,21 @@ pub trait Simulation: Send + Sync {
 
     /// Execute the simulation. Implementations should be safe and non-destructive.
     fn run(&self, ctx: &crate::core::config::Config) -> Result<()>;
+
+    /// ATT&CK techniques this simulation exercises, for filtering and
+    /// ATT&CK Navigator export via `core::registry`. Defaults to empty for
+    /// modules that haven't been annotated yet.
+    fn techniques(&self) -> &'static [Technique] {
+        &[]
+    }
+
+    /// Whether this simulation is safe to run concurrently with other
+    /// simulations under a `--jobs N` worker pool. Defaults to `true`;
+    /// modules that mutate global host state (local accounts, scheduled
+    /// tasks, Defender exclusions, WinRM/service config, ACLs, installed
+    /// software) should override this to `false` so the run loop forces
+    /// them onto the main thread instead of racing them against each other.
+    fn concurrency_safe(&self) -> bool {
+        true
+    }
 }
\ No newline at end of file
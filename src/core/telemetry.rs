@@ -1,13 +1,58 @@
-use crate::core::config::Config;
+//! Telemetry pipeline built on `tracing`.
+//!
+//! Every simulation used to hand-roll its own JSONL + `.log` writing next to
+//! ad-hoc `logger::info!/warn!` calls, so the two could drift. Instead, each
+//! `Simulation::run` wraps its body in `telemetry::scoped(sim_name, test_id,
+//! || { ... })`, which records a thread-local [`RunContext`]. Any `tracing`
+//! event emitted while that scope is active — whether it's a plain
+//! `info!`/`warn!` or a structured `magnet::action` event built from
+//! [`ActionRecord`] — is picked up by [`MagnetTelemetryLayer`] and routed to
+//! `MagnetTelemetry/<sim>_<test_id>.jsonl` and the matching human-readable
+//! `.log`, without the call site having to know the file paths at all.
+
 use anyhow::{Context, Result};
 use dirs::home_dir;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cell::RefCell;
 use std::fs::{create_dir_all, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context as LayerContext, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::core::config::Config;
+use crate::core::simulation::Technique;
+
+/// Owned copy of a `Technique` for embedding in an `ActionRecord`.
+/// `Technique` itself stays `&'static str`-only, since it's a sim's static
+/// ATT&CK metadata; a record's copy needs owned strings because
+/// `MagnetTelemetryLayer` rebuilds it from parsed `tracing` event fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TechniqueRecord {
+    pub id: String,
+    pub tactic: String,
+    pub name: String,
+}
+
+impl From<&Technique> for TechniqueRecord {
+    fn from(t: &Technique) -> Self {
+        Self { id: t.id.into(), tactic: t.tactic.into(), name: t.name.into() }
+    }
+}
+
+/// Convert a simulation's static `techniques()` slice into the owned form an
+/// `ActionRecord` carries.
+pub fn technique_records(techniques: &[Technique]) -> Vec<TechniqueRecord> {
+    techniques.iter().map(TechniqueRecord::from).collect()
+}
 
 /// Struct written for each action executed by Magnet.
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct ActionRecord {
     pub test_id: String,
     pub timestamp: String,
@@ -16,6 +61,59 @@ pub struct ActionRecord {
     pub details: String,
     /// Optional artifact path (e.g., desktop file path)
     pub artifact_path: Option<String>,
+    /// ATT&CK techniques this action maps to, previously hand-concatenated
+    /// into `action` as `"T1082 - T1518 - ..."` strings. Empty for sims that
+    /// haven't been annotated with `Simulation::techniques` yet.
+    pub techniques: Vec<TechniqueRecord>,
+}
+
+/// Which simulation/test-id the current thread's `tracing` events belong to,
+/// plus a running count of warnings emitted in this scope.
+struct RunContext {
+    sim: &'static str,
+    test_id: String,
+    warnings: Arc<AtomicU32>,
+}
+
+thread_local! {
+    static RUN_CTX: RefCell<Option<RunContext>> = RefCell::new(None);
+}
+
+/// Run `f` with the current thread's telemetry context set to `(sim,
+/// test_id)`. Every `tracing` event emitted by `f` (directly, or by anything
+/// it calls) is attributed to this sim/test_id without needing to be passed
+/// a `&Config` explicitly. Nested calls restore the previous context on exit.
+pub fn scoped<T>(sim: &'static str, test_id: &str, f: impl FnOnce() -> T) -> T {
+    let previous = RUN_CTX.with(|c| {
+        c.borrow_mut().replace(RunContext {
+            sim,
+            test_id: test_id.to_string(),
+            warnings: Arc::new(AtomicU32::new(0)),
+        })
+    });
+
+    let result = f();
+
+    RUN_CTX.with(|c| *c.borrow_mut() = previous);
+    result
+}
+
+/// Number of `warn!`/`action_fail` events recorded in the current thread's
+/// telemetry scope.
+pub fn warning_count() -> u32 {
+    RUN_CTX.with(|c| c.borrow().as_ref().map(|r| r.warnings.load(Ordering::Relaxed)).unwrap_or(0))
+}
+
+fn current_sim_and_test_id() -> Option<(&'static str, String)> {
+    RUN_CTX.with(|c| c.borrow().as_ref().map(|r| (r.sim, r.test_id.clone())))
+}
+
+fn bump_warning_count() {
+    RUN_CTX.with(|c| {
+        if let Some(r) = c.borrow().as_ref() {
+            r.warnings.fetch_add(1, Ordering::Relaxed);
+        }
+    });
 }
 
 /// Get the telemetry directory: %USERPROFILE%\Documents\MagnetTelemetry
@@ -29,44 +127,170 @@ pub fn telemetry_dir() -> Option<PathBuf> {
     }
 }
 
-/// Write both JSONL and human-readable log for an ActionRecord.
-/// Non-fatal: returns an Err if it couldn't write.
-pub fn write_action_record(cfg: &Config, rec: &ActionRecord) -> Result<()> {
-    let dir = telemetry_dir().ok_or_else(|| anyhow::anyhow!("could not determine telemetry output path"))?;
-    create_dir_all(&dir).with_context(|| format!("creating telemetry directory {}", dir.display()))?;
+/// Emit an `ActionRecord` as a structured `tracing` event. The actual
+/// JSONL/human-log writing happens in [`MagnetTelemetryLayer`], which is
+/// installed once by [`init_subscriber`] — this function just needs to exist
+/// so call sites don't each reimplement file I/O.
+pub fn write_action_record(_cfg: &Config, rec: &ActionRecord) -> Result<()> {
+    let techniques_json = serde_json::to_string(&rec.techniques).unwrap_or_else(|_| "[]".to_string());
+    tracing::info!(
+        target: "magnet::action",
+        test_id = %rec.test_id,
+        action = %rec.action,
+        status = %rec.status,
+        details = %rec.details,
+        artifact_path = rec.artifact_path.as_deref().unwrap_or(""),
+        techniques = %techniques_json,
+    );
+    Ok(())
+}
 
-    // JSONL file
-    let mut jsonl_path = dir.clone();
-    jsonl_path.push(format!("magnet_actions_{}.jsonl", cfg.test_id));
-    let mut jf = OpenOptions::new()
+fn append_jsonl(dir: &PathBuf, prefix: &str, test_id: &str, rec: &ActionRecord) -> Result<()> {
+    create_dir_all(dir).with_context(|| format!("creating telemetry directory {}", dir.display()))?;
+    let mut path = dir.clone();
+    path.push(format!("{}_{}.jsonl", prefix, test_id));
+    let mut f = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(&jsonl_path)
-        .with_context(|| format!("opening telemetry file {}", jsonl_path.display()))?;
-    let j = serde_json::to_string(rec)?;
-    writeln!(jf, "{}", j)?;
-
-    // Human-readable log
-    let mut log_path = dir;
-    log_path.push(format!("magnet_actions_{}.log", cfg.test_id));
-    let mut lf = OpenOptions::new()
+        .open(&path)
+        .with_context(|| format!("opening telemetry file {}", path.display()))?;
+    writeln!(f, "{}", serde_json::to_string(rec)?)?;
+    Ok(())
+}
+
+fn append_human_log(dir: &PathBuf, prefix: &str, test_id: &str, rec: &ActionRecord) -> Result<()> {
+    create_dir_all(dir).with_context(|| format!("creating telemetry directory {}", dir.display()))?;
+    let mut path = dir.clone();
+    path.push(format!("{}_{}.log", prefix, test_id));
+    let mut f = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(&log_path)
-        .with_context(|| format!("opening human log {}", log_path.display()))?;
-
-    writeln!(lf, "================================================================")?;
-    writeln!(lf, "TEST ID   : {}", rec.test_id)?;
-    writeln!(lf, "TIMESTAMP : {}", rec.timestamp)?;
-    writeln!(lf, "ACTION    : {}", rec.action)?;
-    writeln!(lf, "STATUS    : {}", rec.status)?;
+        .open(&path)
+        .with_context(|| format!("opening human log {}", path.display()))?;
+
+    writeln!(f, "================================================================")?;
+    writeln!(f, "TEST ID   : {}", rec.test_id)?;
+    writeln!(f, "TIMESTAMP : {}", rec.timestamp)?;
+    writeln!(f, "ACTION    : {}", rec.action)?;
+    writeln!(f, "STATUS    : {}", rec.status)?;
     if !rec.details.is_empty() {
-        writeln!(lf, "DETAILS   : {}", rec.details)?;
+        writeln!(f, "DETAILS   : {}", rec.details)?;
     }
-    if let Some(path) = &rec.artifact_path {
-        writeln!(lf, "ARTIFACT  : {}", path)?;
+    if let Some(path_) = &rec.artifact_path {
+        writeln!(f, "ARTIFACT  : {}", path_)?;
     }
-    writeln!(lf)?;
-
+    if !rec.techniques.is_empty() {
+        let ids: Vec<&str> = rec.techniques.iter().map(|t| t.id.as_str()).collect();
+        writeln!(f, "ATT&CK    : {}", ids.join(", "))?;
+    }
+    writeln!(f)?;
     Ok(())
 }
+
+/// Collects a `tracing` event's fields into a `serde_json::Map`.
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::String(value.to_string()));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::Bool(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::String(format!("{:?}", value)));
+    }
+}
+
+/// Layer emitting structured JSON events to `<sim>_<test_id>.jsonl` and a
+/// parallel human-readable banner to `<sim>_<test_id>.log`. Falls back to the
+/// `magnet_actions_<test_id>` prefix when no sim scope is active (e.g. a
+/// top-level `logger::info!` outside any `Simulation::run`).
+pub struct MagnetTelemetryLayer;
+
+impl<S> Layer<S> for MagnetTelemetryLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+        if *event.metadata().level() == Level::WARN {
+            bump_warning_count();
+        }
+
+        let Some(dir) = telemetry_dir() else { return };
+
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+
+        let (sim, test_id) = current_sim_and_test_id().unwrap_or(("magnet", "unknown".to_string()));
+        let prefix = sim.replace("::", "_");
+        let timestamp = chrono::Utc::now().to_rfc3339();
+
+        if event.metadata().target() == "magnet::action" {
+            let rec = ActionRecord {
+                test_id: test_id.clone(),
+                timestamp,
+                action: visitor.0.get("action").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                status: visitor.0.get("status").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                details: visitor.0.get("details").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                artifact_path: visitor
+                    .0
+                    .get("artifact_path")
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                techniques: visitor
+                    .0
+                    .get("techniques")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| serde_json::from_str(s).ok())
+                    .unwrap_or_default(),
+            };
+            let _ = append_jsonl(&dir, &prefix, &test_id, &rec);
+            let _ = append_human_log(&dir, &prefix, &test_id, &rec);
+            return;
+        }
+
+        // Generic info!/warn!/error! events: append a plain JSON line so the
+        // two log styles never drift out of sync with each other.
+        let mut obj = Map::new();
+        obj.insert("test_id".into(), Value::String(test_id.clone()));
+        obj.insert("level".into(), Value::String(event.metadata().level().to_string()));
+        obj.insert("message".into(), visitor.0.get("message").cloned().unwrap_or(Value::Null));
+        let _ = create_dir_all(&dir);
+        let mut path = dir;
+        path.push(format!("{}_{}.jsonl", prefix, test_id));
+        if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(&path) {
+            let _ = writeln!(f, "{}", Value::Object(obj));
+        }
+    }
+}
+
+/// Install the layered `tracing` subscriber: the structured JSON/human-log
+/// layer above is always on. The plain `fmt` layer for stdout (and, via its
+/// `with_writer`, any syslog/stdout sink a deployment wants to swap in) is
+/// opt-in via `MAGNET_TRACING_STDOUT=1` — `core::logger`'s `println!` calls
+/// are already every event's console rendering, so leaving this layer on by
+/// default would print each action twice and, under `--output json`,
+/// interleave un-tagged prose into the NDJSON event stream.
+/// Safe to call more than once; only the first call takes effect.
+pub fn init_subscriber() {
+    use tracing_subscriber::prelude::*;
+
+    let stdout_enabled = std::env::var("MAGNET_TRACING_STDOUT").map(|v| v == "1").unwrap_or(false);
+    let stdout_layer = stdout_enabled
+        .then(|| tracing_subscriber::fmt::layer().with_target(false).with_level(true).compact());
+
+    let _ = tracing_subscriber::registry().with(MagnetTelemetryLayer).with(stdout_layer).try_init();
+}
@@ -0,0 +1,152 @@
+//! Native Windows API collection backend.
+//!
+//! Provides process, service, and Defender-preference enumeration using the
+//! Win32 API directly (in the spirit of a win-api-wrappers layer) instead of
+//! shelling out to `wmic`/`powershell.exe`. Simulations should prefer these
+//! helpers and only fall back to spawning a process when a call here fails.
+
+#![cfg(windows)]
+
+use anyhow::{anyhow, Result};
+use std::ffi::CStr;
+use std::mem::size_of;
+use std::process::Command;
+
+use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+use winapi::um::tlhelp32::{
+    CreateToolhelp32Snapshot, Process32First, Process32Next, PROCESSENTRY32, TH32CS_SNAPPROCESS,
+};
+use winapi::um::winsvc::{
+    CloseServiceHandle, EnumServicesStatusExA, OpenSCManagerA, ENUM_SERVICE_STATUS_PROCESSA,
+    SC_ENUM_PROCESS_INFO, SC_MANAGER_ENUMERATE_SERVICE, SERVICE_STATE_ALL, SERVICE_WIN32,
+};
+
+/// Enumerate running process names via `CreateToolhelp32Snapshot`.
+pub fn list_processes() -> Result<Vec<String>> {
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return Err(anyhow!("CreateToolhelp32Snapshot failed"));
+        }
+
+        let mut entry: PROCESSENTRY32 = std::mem::zeroed();
+        entry.dwSize = size_of::<PROCESSENTRY32>() as u32;
+
+        let mut names = Vec::new();
+        if Process32First(snapshot, &mut entry) != 0 {
+            loop {
+                let name = CStr::from_ptr(entry.szExeFile.as_ptr())
+                    .to_string_lossy()
+                    .into_owned();
+                names.push(name);
+
+                if Process32Next(snapshot, &mut entry) == 0 {
+                    break;
+                }
+            }
+        }
+
+        CloseHandle(snapshot);
+        Ok(names)
+    }
+}
+
+/// Enumerate service display/service names through the Service Control Manager.
+pub fn list_services() -> Result<Vec<String>> {
+    unsafe {
+        let scm = OpenSCManagerA(std::ptr::null(), std::ptr::null(), SC_MANAGER_ENUMERATE_SERVICE);
+        if scm.is_null() {
+            return Err(anyhow!("OpenSCManager failed"));
+        }
+
+        // First pass with a zero-sized buffer to learn the required size.
+        let mut bytes_needed: u32 = 0;
+        let mut services_returned: u32 = 0;
+        let mut resume_handle: u32 = 0;
+        EnumServicesStatusExA(
+            scm,
+            SC_ENUM_PROCESS_INFO,
+            SERVICE_WIN32,
+            SERVICE_STATE_ALL,
+            std::ptr::null_mut(),
+            0,
+            &mut bytes_needed,
+            &mut services_returned,
+            &mut resume_handle,
+            std::ptr::null(),
+        );
+
+        if bytes_needed == 0 {
+            CloseServiceHandle(scm);
+            return Ok(Vec::new());
+        }
+
+        let mut buf: Vec<u8> = vec![0; bytes_needed as usize];
+        let ok = EnumServicesStatusExA(
+            scm,
+            SC_ENUM_PROCESS_INFO,
+            SERVICE_WIN32,
+            SERVICE_STATE_ALL,
+            buf.as_mut_ptr(),
+            bytes_needed,
+            &mut bytes_needed,
+            &mut services_returned,
+            &mut resume_handle,
+            std::ptr::null(),
+        );
+        CloseServiceHandle(scm);
+
+        if ok == 0 {
+            return Err(anyhow!("EnumServicesStatusExA failed"));
+        }
+
+        let entries = buf.as_ptr() as *const ENUM_SERVICE_STATUS_PROCESSA;
+        let mut names = Vec::with_capacity(services_returned as usize);
+        for i in 0..services_returned as isize {
+            let entry = &*entries.offset(i);
+            let name = CStr::from_ptr(entry.lpDisplayName).to_string_lossy().into_owned();
+            names.push(name);
+        }
+
+        Ok(names)
+    }
+}
+
+/// Query Windows Defender exclusion paths without spawning a shell.
+///
+/// There is no documented Win32 API for reading Defender preferences, so this
+/// reads the same registry values `Get-MpPreference` surfaces
+/// (`HKLM\SOFTWARE\Microsoft\Windows Defender\Exclusions\Paths`) via the
+/// registry APIs, which avoids the `powershell.exe` child process entirely.
+pub fn defender_exclusion_paths() -> Result<Vec<String>> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let key = hklm
+        .open_subkey(r"SOFTWARE\Microsoft\Windows Defender\Exclusions\Paths")
+        .map_err(|e| anyhow!("opening Defender exclusions key failed: {}", e))?;
+
+    Ok(key.enum_values().filter_map(|v| v.ok()).map(|(name, _)| name).collect())
+}
+
+/// Run a closure that calls into the Win32 API, falling back to `fallback` (typically a
+/// `wmic`/`powershell.exe` invocation) if it returns an error. Used so detection keeps
+/// working on hosts where the legacy tools have been removed, while still degrading
+/// gracefully on hosts where the raw API calls are blocked.
+pub fn with_fallback<T>(primary: impl FnOnce() -> Result<T>, fallback: impl FnOnce() -> Result<T>) -> Result<T> {
+    match primary() {
+        Ok(v) => Ok(v),
+        Err(_) => fallback(),
+    }
+}
+
+/// Spawn helper kept here so callers that must fall back to a shell command share one
+/// narrow place that does it, rather than each simulation hand-rolling `Command::new`.
+pub fn run_legacy_command(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| anyhow!("failed to run {}: {}", program, e))?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -4,12 +4,19 @@
 mod core;
 mod platforms;
 
-use anyhow::Result;
-use clap::{Parser, Subcommand, CommandFactory};
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand, ValueEnum, CommandFactory};
 use colored::Colorize;
 use core::config::Config;
+use core::events::RunEvent;
 use core::logger;
+use core::run_report::{ModuleReportEntry, RunReport};
 use core::runner::Runner;
+use core::scenario::{OnError, Scenario};
+use core::simulation::Simulation;
+use chrono::{DateTime, Utc};
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::Instant;
 
 /// CLI definition using clap (OS-namespaced subcommands)
@@ -17,17 +24,43 @@ use std::time::Instant;
 #[command(name = "magnet")]
 #[command(about = "Magnet — cross-platform purple-team simulation toolkit", long_about = None)]
 struct Cli {
+    /// Output format: colorized prose for a human, or one JSON event per
+    /// line on stdout for orchestrators/detection-validation harnesses
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human, global = true)]
+    output: OutputFormat,
+
+    /// Run up to N independent modules concurrently (default 1, sequential).
+    /// Modules marked `concurrency_safe() == false` (accounts, scheduled
+    /// tasks, Defender exclusions, WinRM, ACLs, software installs) always
+    /// run serially on the main thread regardless of this value.
+    #[arg(long, default_value_t = 1, global = true)]
+    jobs: usize,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Human,
+    Json,
+}
+
 #[derive(Debug, Subcommand)]
 enum Commands {
-    /// List available modules (optionally filter by OS)
+    /// List available modules (optionally filter by OS, ATT&CK technique, or tactic)
     List {
         /// OS namespace to filter by (e.g. windows, linux)
         #[arg(long, short)]
         os: Option<String>,
+
+        /// Only list modules that exercise this ATT&CK technique id (e.g. T1082)
+        #[arg(long)]
+        technique: Option<String>,
+
+        /// Only list modules whose techniques fall under this ATT&CK tactic (e.g. Discovery)
+        #[arg(long)]
+        tactic: Option<String>,
     },
 
     /// Run modules. Usage: `magnet run windows all` or `magnet run windows ransom_note discovery_sim`
@@ -38,15 +71,64 @@ enum Commands {
         /// Modules to run (module short name like `ransom_note` or full `windows::ransom_note`). Use `all` to run every module under the OS.
         modules: Vec<String>,
     },
+
+    /// Run a chained attack sequence described by a scenario file (JSON or YAML)
+    Scenario {
+        /// Path to the scenario file
+        file: PathBuf,
+    },
+
+    /// Interactively generate a config file at the standard config path
+    /// (`Config::config_path()`), read by every subsequent `Config::load()`
+    Init,
+
+    /// Queue modules to fire at absolute times instead of each one sleeping
+    /// in place, via `core::scheduler::Scheduler`'s on-disk schedule.
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ScheduleAction {
+    /// Queue `module` to run at `at` (RFC3339, e.g. 2026-07-30T18:00:00Z)
+    Enqueue {
+        /// Module to run (short or full name, e.g. `ransom_note` or `windows::ransom_note`)
+        module: String,
+
+        /// Absolute release time, RFC3339
+        #[arg(long)]
+        at: String,
+    },
+
+    /// List every queued entry
+    List,
+
+    /// Remove queued entries whose release time falls in [from, until]
+    /// (either bound may be omitted to leave that side open)
+    Delete {
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// Run every entry whose release time has passed and remove it from the schedule
+    RunDue,
 }
 
 fn main() -> Result<()> {
-    // init logger & header
+    // init logger
     logger::init();
-    logger::header(env!("CARGO_PKG_VERSION"));
 
     // parse CLI
     let cli = Cli::parse();
+
+    if cli.output == OutputFormat::Human {
+        logger::header(env!("CARGO_PKG_VERSION"));
+    }
+
     // If no command provided, print help and exit (don't run anything)
     if cli.command.is_none() {
         println!();
@@ -58,13 +140,16 @@ fn main() -> Result<()> {
 
     // start timer (we still measure overall execution when running)
     let start_time = Instant::now();
+    let run_started_at = Utc::now();
 
     // load config
     let config = Config::load().unwrap_or_default();
 
-    // show common paths (Windows only)
+    // show common paths (Windows only) — human mode only, so `--output json`
+    // consumers parsing stdout line-by-line don't choke on non-JSON prose
+    // ahead of the first `RunEvent`.
     #[cfg(target_os = "windows")]
-    {
+    if cli.output == OutputFormat::Human {
         if let Some(path) = dirs::desktop_dir() {
             println!("{} {}", "📁 Desktop:".bright_cyan(), path.display());
         }
@@ -140,14 +225,24 @@ fn main() -> Result<()> {
 
     // Helper: collect modules grouped by OS
     let modules_by_os = collect_modules_by_os(&runner);
+    let output = cli.output;
+    let jobs = cli.jobs.max(1);
+
+    // Populated by the `Run`/`Scenario`/default arms below, and by
+    // `Schedule RunDue` when it actually executes due modules; used after
+    // the match to write and upload an aggregated run report and decide the
+    // exit code. `List`/`Init`/other `Schedule` actions leave this `None` —
+    // no report is written for them.
+    let mut report_modules: Option<Vec<ModuleReportEntry>> = None;
 
     // Decide command:
     match cli.command {
-        Some(Commands::List { os }) => {
+        Some(Commands::List { os, technique, tactic }) => {
+            let filtered = filter_by_attck(&modules_by_os, technique.as_deref(), tactic.as_deref());
             if let Some(os) = os {
-                list_modules_for_os(&modules_by_os, &os);
+                list_modules_for_os(&filtered, &os);
             } else {
-                list_all_modules(&modules_by_os);
+                list_all_modules(&filtered);
             }
         }
 
@@ -158,21 +253,38 @@ fn main() -> Result<()> {
             } else {
                 modules
             };
-            run_selected(&mut runner, &modules_by_os, &os, &requested)?;
+            report_modules = Some(run_selected(&mut runner, &modules_by_os, &os, &requested, output, jobs)?);
+        }
+
+        Some(Commands::Scenario { file }) => {
+            let scenario = Scenario::load(&file)?;
+            report_modules = Some(run_scenario(&mut runner, &modules_by_os, &scenario, output)?);
+        }
+
+        Some(Commands::Init) => {
+            run_init_wizard()?;
+        }
+
+        Some(Commands::Schedule { action }) => {
+            report_modules = run_schedule_command(&mut runner, &modules_by_os, action, output)?;
         }
 
         None => {
             // default behavior: run all modules for current OS (Windows)
             #[cfg(target_os = "windows")]
             {
-                println!();
-                println!("{}", "▶ Running simulations...".bright_green().bold());
-                run_selected(
+                if output == OutputFormat::Human {
+                    println!();
+                    println!("{}", "▶ Running simulations...".bright_green().bold());
+                }
+                report_modules = Some(run_selected(
                     &mut runner,
                     &modules_by_os,
                     "windows",
                     &vec!["all".to_string()],
-                )?;
+                    output,
+                    jobs,
+                )?);
             }
 
             #[cfg(not(target_os = "windows"))]
@@ -183,12 +295,58 @@ fn main() -> Result<()> {
     }
 
     // summary
-    let elapsed = start_time.elapsed();
-    logger::summary(elapsed);
+    if output == OutputFormat::Human {
+        let elapsed = start_time.elapsed();
+        logger::summary(elapsed);
+    }
+
+    // Aggregated run report: written to the telemetry directory, optionally
+    // POSTed to `Config::report_endpoint`, and used to set a non-zero exit
+    // code so a scheduled validation pipeline can gate on it instead of
+    // parsing logs.
+    if let Some(modules) = report_modules {
+        let report = RunReport::build(&runner.config, run_started_at, modules);
+
+        if let Err(e) = report.write(&runner.config) {
+            logger::warn(&format!("failed to write run report: {}", e));
+        }
+        if let Err(e) = report.upload(&runner.config) {
+            logger::warn(&format!("failed to upload run report: {}", e));
+        }
+
+        if report.any_failed() {
+            std::process::exit(1);
+        }
+    }
 
     Ok(())
 }
 
+/// Narrow a modules-by-OS map down to modules matching `technique` and/or
+/// `tactic` (via `core::registry`), leaving it untouched if both are `None`.
+fn filter_by_attck(
+    map: &std::collections::BTreeMap<String, Vec<String>>,
+    technique: Option<&str>,
+    tactic: Option<&str>,
+) -> std::collections::BTreeMap<String, Vec<String>> {
+    if technique.is_none() && tactic.is_none() {
+        return map.clone();
+    }
+
+    let allowed: std::collections::HashSet<&'static str> = core::registry::SimId::iter()
+        .filter(|s| technique.map_or(true, |t| s.techniques().iter().any(|tech| tech.id == t)))
+        .filter(|s| tactic.map_or(true, |t| s.techniques().iter().any(|tech| tech.tactic.eq_ignore_ascii_case(t))))
+        .map(|s| s.full_name())
+        .collect();
+
+    map.iter()
+        .filter_map(|(os, mods)| {
+            let kept: Vec<String> = mods.iter().filter(|m| allowed.contains(m.as_str())).cloned().collect();
+            if kept.is_empty() { None } else { Some((os.clone(), kept)) }
+        })
+        .collect()
+}
+
 /// Build a map of OS -> Vec<module_full_name>
 fn collect_modules_by_os(runner: &Runner) -> std::collections::BTreeMap<String, Vec<String>> {
     let mut map: std::collections::BTreeMap<String, Vec<String>> = Default::default();
@@ -235,20 +393,29 @@ fn list_modules_for_os(map: &std::collections::BTreeMap<String, Vec<String>>, os
     }
 }
 
-/// Run selected modules for the given OS. requested may contain short names, full names, or "all".
+/// Run selected modules for the given OS. requested may contain short names,
+/// full names, or "all". Returns the per-module report entries so the caller
+/// can build an aggregated `RunReport`.
 fn run_selected(
     runner: &mut Runner,
     modules_by_os: &std::collections::BTreeMap<String, Vec<String>>,
     os: &str,
     requested: &Vec<String>,
-) -> Result<()> {
+    output: OutputFormat,
+    jobs: usize,
+) -> Result<Vec<ModuleReportEntry>> {
     let os_key = os.to_string();
 
     let available = match modules_by_os.get(&os_key) {
         Some(v) => v.clone(),
         None => {
-            println!("{}", format!("No modules available for OS '{}'", os).bright_red());
-            return Ok(());
+            let msg = format!("No modules available for OS '{}'", os);
+            if output == OutputFormat::Human {
+                println!("{}", msg.bright_red());
+            } else {
+                eprintln!("{}", msg);
+            }
+            return Ok(Vec::new());
         }
     };
 
@@ -269,11 +436,12 @@ fn run_selected(
                 .collect();
 
             if matches.is_empty() {
-                println!(
-                    "{} {}",
-                    "⚠".bright_yellow(),
-                    format!("Module '{}' not found under {}", r, os).bright_yellow()
-                );
+                let msg = format!("Module '{}' not found under {}", r, os);
+                if output == OutputFormat::Human {
+                    println!("{} {}", "⚠".bright_yellow(), msg.bright_yellow());
+                } else {
+                    eprintln!("⚠ {}", msg);
+                }
             } else {
                 to_run.extend(matches);
             }
@@ -281,38 +449,582 @@ fn run_selected(
     }
 
     if to_run.is_empty() {
-        println!("{}", "No modules to run.".bright_yellow());
-        return Ok(());
+        if output == OutputFormat::Human {
+            println!("{}", "No modules to run.".bright_yellow());
+        } else {
+            eprintln!("No modules to run.");
+        }
+        return Ok(Vec::new());
     }
 
-    // Print selected modules
-    println!();
-    println!(
-        "{} {}",
-        "▶ Selected modules:".bright_green().bold(),
-        to_run.join(", ").dimmed()
-    );
-
-    // Execute each selected module in order (use logger's module_start for headers)
-    for module_full in to_run {
-        logger::module_start(&module_full);
-
-        // find the simulation instance by full name and execute it
-        let found = runner
+    match output {
+        OutputFormat::Human => {
+            println!();
+            println!(
+                "{} {}",
+                "▶ Selected modules:".bright_green().bold(),
+                to_run.join(", ").dimmed()
+            );
+        }
+        OutputFormat::Json => {
+            RunEvent::Plan { modules: to_run.clone(), total: to_run.len() }.emit();
+        }
+    }
+
+    if jobs <= 1 {
+        // Execute each selected module in order (use logger's module_start for headers)
+        let mut reports = Vec::new();
+        for module_full in to_run {
+            let sim = runner.simulations.iter().find(|s| s.name().eq_ignore_ascii_case(&module_full));
+            reports.push(run_module_streaming(sim.map(|s| s.as_ref()), &runner.config, &module_full, output));
+        }
+        return Ok(reports);
+    }
+
+    // --jobs > 1: force modules with global side effects onto the main
+    // thread, one at a time, while the rest fan out across a bounded pool
+    // of worker threads pulling from a shared queue. Note that a module's
+    // own `run()` still prints its live progress via `core::logger` as it
+    // executes (action_running/action_ok/...), so concurrent modules can
+    // interleave that prose on stdout; the module_start/result lines below
+    // are reported afterward, in deterministic name order, as the reliable
+    // summary.
+    let (serial, parallel): (Vec<String>, Vec<String>) = to_run.into_iter().partition(|m| {
+        runner
             .simulations
             .iter()
-            .find(|s| s.name().eq_ignore_ascii_case(&module_full));
+            .find(|s| s.name().eq_ignore_ascii_case(m))
+            .map(|s| !s.concurrency_safe())
+            .unwrap_or(false)
+    });
 
-        if let Some(sim) = found {
-            // run simulation; runner.config is Arc<Config>, so deref to &Config
-            if let Err(e) = sim.run(&*runner.config) {
-                logger::error(&format!("module '{}' failed: {}", module_full, e));
+    let mut reports = Vec::new();
+    for module_full in serial {
+        let sim = runner.simulations.iter().find(|s| s.name().eq_ignore_ascii_case(&module_full));
+        reports.push(run_module_streaming(sim.map(|s| s.as_ref()), &runner.config, &module_full, output));
+    }
+
+    if !parallel.is_empty() {
+        let queue = std::sync::Mutex::new(std::collections::VecDeque::from(parallel));
+        let (tx, rx) = std::sync::mpsc::channel::<(String, String, u128, Option<String>)>();
+        let sims = &runner.simulations;
+        let cfg = &runner.config;
+
+        std::thread::scope(|scope| {
+            for _ in 0..jobs {
+                let queue = &queue;
+                let tx = tx.clone();
+                scope.spawn(move || loop {
+                    let module_full = match queue.lock().unwrap().pop_front() {
+                        Some(m) => m,
+                        None => break,
+                    };
+                    let sim = sims.iter().find(|s| s.name().eq_ignore_ascii_case(&module_full));
+                    let (status, error, duration_ms) =
+                        execute_module(sim.map(|s| s.as_ref()), cfg, &module_full, output);
+                    let _ = tx.send((module_full, status, duration_ms, error));
+                });
             }
-        } else {
+            drop(tx);
+        });
+
+        let mut results: Vec<(String, String, u128, Option<String>)> = rx.iter().collect();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (name, status, duration_ms, error) in results {
+            match output {
+                // The module's own failure/panic was already logged live
+                // (from its worker thread) as it happened; this pass just
+                // reports the aggregate header and status in deterministic
+                // name order, without repeating that message.
+                OutputFormat::Human => {
+                    logger::module_start(&name);
+                    logger::info(&format!("{} ({} ms)", status, duration_ms));
+                }
+                OutputFormat::Json => {
+                    RunEvent::ModuleStart { name: name.clone() }.emit();
+                    RunEvent::ModuleResult {
+                        name: name.clone(),
+                        status: status.clone(),
+                        duration_ms,
+                        error: error.clone(),
+                    }
+                    .emit();
+                }
+            }
+            reports.push(ModuleReportEntry { name, status, duration_ms, error });
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Run one module to completion, emitting its `ModuleStart`/`ModuleResult`
+/// immediately (streaming), for the sequential (jobs <= 1 or serial-forced)
+/// path. Returns the report entry for the aggregated `RunReport`.
+fn run_module_streaming(
+    sim: Option<&dyn Simulation>,
+    cfg: &Config,
+    module_full: &str,
+    output: OutputFormat,
+) -> ModuleReportEntry {
+    match output {
+        OutputFormat::Human => logger::module_start(module_full),
+        OutputFormat::Json => RunEvent::ModuleStart { name: module_full.to_string() }.emit(),
+    }
+
+    let (status, error, duration_ms) = execute_module(sim, cfg, module_full, output);
+
+    if output == OutputFormat::Json {
+        RunEvent::ModuleResult {
+            name: module_full.to_string(),
+            status: status.clone(),
+            duration_ms,
+            error: error.clone(),
+        }
+        .emit();
+    }
+
+    ModuleReportEntry { name: module_full.to_string(), status, duration_ms, error }
+}
+
+/// Run a single module's simulation and classify the outcome. Logs failures
+/// via `logger` (human) or `tracing` (json, so the telemetry subscriber
+/// still sees them) but does not print `ModuleStart`/`ModuleResult` itself —
+/// callers decide whether to stream those immediately or buffer them for
+/// deterministic ordering after a concurrent batch joins.
+fn execute_module(
+    sim: Option<&dyn Simulation>,
+    cfg: &Config,
+    module_full: &str,
+    output: OutputFormat,
+) -> (String, Option<String>, u128) {
+    let start = Instant::now();
+
+    let sim = match sim {
+        Some(sim) => sim,
+        None => {
             // fallback — shouldn't happen because we built 'available' earlier
-            logger::warn(&format!("simulation implementation for '{}' not found", module_full));
+            let msg = format!("simulation implementation for '{}' not found", module_full);
+            if output == OutputFormat::Human {
+                logger::warn(&msg);
+            } else {
+                tracing::warn!(message = %msg);
+            }
+            return (
+                "failed".to_string(),
+                Some("simulation implementation not found".to_string()),
+                start.elapsed().as_millis(),
+            );
+        }
+    };
+
+    // Catch panics so one module blowing up under `--jobs` doesn't unwind
+    // through `thread::scope` and discard the rest of that batch's
+    // already-computed results.
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sim.run(cfg)));
+    let (status, error) = match outcome {
+        Ok(Ok(())) if cfg.dry_run => ("dry-run".to_string(), None),
+        Ok(Ok(())) => ("ok".to_string(), None),
+        Ok(Err(e)) => {
+            let msg = format!("module '{}' failed: {}", module_full, e);
+            if output == OutputFormat::Human {
+                logger::error(&msg);
+            } else {
+                tracing::error!(message = %msg);
+            }
+            ("failed".to_string(), Some(e.to_string()))
+        }
+        Err(panic) => {
+            let panic_msg = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            let msg = format!("module '{}' panicked: {}", module_full, panic_msg);
+            if output == OutputFormat::Human {
+                logger::error(&msg);
+            } else {
+                tracing::error!(message = %msg);
+            }
+            ("failed".to_string(), Some(panic_msg))
+        }
+    };
+
+    (status, error, start.elapsed().as_millis())
+}
+
+/// Run a scenario's steps in declared order (not registration order),
+/// resolving each step's module against `modules_by_os` the same way
+/// `run_selected` does, overriding `runner.config` per-step, and stopping
+/// or continuing past a failed step per its `on_error` setting.
+///
+/// Every step — including one that can't even be resolved to a module —
+/// becomes a `ModuleReportEntry`, and an `Abort` step stops the loop rather
+/// than returning `Err` early, so the caller always gets a full `Vec` to
+/// build a `RunReport` from and gate the process exit code on, the same way
+/// `run_selected` does. A scenario where every step fails with
+/// `on_error: continue` now reports those failures instead of silently
+/// exiting 0.
+///
+/// `output` is threaded through the same way `run_selected` does: under
+/// `OutputFormat::Json` every step emits `RunEvent::Plan`/`ModuleStart`/
+/// `ModuleResult` instead of colorized prose, so `--output json scenario`
+/// produces the same NDJSON stream as `--output json run`.
+fn run_scenario(
+    runner: &mut Runner,
+    modules_by_os: &std::collections::BTreeMap<String, Vec<String>>,
+    scenario: &Scenario,
+    output: OutputFormat,
+) -> Result<Vec<ModuleReportEntry>> {
+    if output == OutputFormat::Human {
+        println!();
+        println!(
+            "{} {}",
+            "▶ Running scenario:".bright_green().bold(),
+            format!("{} step(s)", scenario.steps.len()).dimmed()
+        );
+    } else {
+        // Resolve each step's module the same way the loop below does, so
+        // `Plan.modules` matches the `name` the later `ModuleStart`/
+        // `ModuleResult` events for that step carry — falling back to the
+        // raw (possibly short) name when a step can't be resolved yet, same
+        // as an unresolvable step reports in its own `ModuleReportEntry`.
+        let modules: Vec<String> = scenario
+            .steps
+            .iter()
+            .map(|step| {
+                modules_by_os
+                    .get(&step.os)
+                    .and_then(|available| {
+                        available
+                            .iter()
+                            .find(|m| {
+                                m.eq_ignore_ascii_case(&step.module)
+                                    || m.split("::").last().map(|s| s.eq_ignore_ascii_case(&step.module)).unwrap_or(false)
+                            })
+                            .cloned()
+                    })
+                    .unwrap_or_else(|| step.module.clone())
+            })
+            .collect();
+        RunEvent::Plan { total: modules.len(), modules }.emit();
+    }
+
+    let mut reports = Vec::new();
+
+    for (i, step) in scenario.steps.iter().enumerate() {
+        let available = match modules_by_os.get(&step.os) {
+            Some(v) => v,
+            None => {
+                let msg = format!("step {}: no modules available for OS '{}'", i + 1, step.os);
+                if output == OutputFormat::Human {
+                    logger::error(&msg);
+                } else {
+                    tracing::error!(message = %msg);
+                }
+                reports.push(ModuleReportEntry {
+                    name: step.module.clone(),
+                    status: "failed".to_string(),
+                    duration_ms: 0,
+                    error: Some(msg),
+                });
+                if step.on_error == OnError::Abort {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        let module_full = available
+            .iter()
+            .find(|m| {
+                m.eq_ignore_ascii_case(&step.module)
+                    || m.split("::").last().map(|s| s.eq_ignore_ascii_case(&step.module)).unwrap_or(false)
+            })
+            .cloned();
+
+        let module_full = match module_full {
+            Some(m) => m,
+            None => {
+                let msg = format!("step {}: module '{}' not found under {}", i + 1, step.module, step.os);
+                if output == OutputFormat::Human {
+                    logger::error(&msg);
+                } else {
+                    tracing::error!(message = %msg);
+                }
+                reports.push(ModuleReportEntry {
+                    name: step.module.clone(),
+                    status: "failed".to_string(),
+                    duration_ms: 0,
+                    error: Some(msg),
+                });
+                if step.on_error == OnError::Abort {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        match output {
+            OutputFormat::Human => logger::module_start(&module_full),
+            OutputFormat::Json => RunEvent::ModuleStart { name: module_full.clone() }.emit(),
+        }
+
+        let sim = runner.simulations.iter().find(|s| s.name().eq_ignore_ascii_case(&module_full));
+        let step_cfg = step.config.apply(&*runner.config);
+        let (status, error, duration_ms) = execute_module(sim.map(|s| s.as_ref()), &step_cfg, &module_full, output);
+
+        if output == OutputFormat::Json {
+            RunEvent::ModuleResult {
+                name: module_full.clone(),
+                status: status.clone(),
+                duration_ms,
+                error: error.clone(),
+            }
+            .emit();
+        }
+
+        let failed = status == "failed";
+        reports.push(ModuleReportEntry { name: module_full, status, duration_ms, error });
+
+        if failed && step.on_error == OnError::Abort {
+            break;
+        }
+
+        if i + 1 < scenario.steps.len() {
+            if let Some(delay_ms) = step.delay_ms {
+                std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+            }
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Handle `magnet schedule ...` — the CLI surface for `core::scheduler`.
+/// Previously `Scheduler` was only ever driven internally by
+/// `scheduled_task_sim`'s own `insert_at` + busy-wait for its own artifact
+/// check; `delete_within`/`list_within` had no caller at all. This gives an
+/// operator a real way to queue a module for an absolute release time, see
+/// and prune what's queued, and release whatever's come due, instead of
+/// every timed step being hardcoded to block in place.
+///
+/// Returns `Some(reports)` only for `RunDue`, which is the only action that
+/// executes modules — the caller folds that into the same `RunReport`/exit
+/// code path as `run_selected`/`run_scenario`.
+fn run_schedule_command(
+    runner: &mut Runner,
+    modules_by_os: &std::collections::BTreeMap<String, Vec<String>>,
+    action: ScheduleAction,
+    output: OutputFormat,
+) -> Result<Option<Vec<ModuleReportEntry>>> {
+    let path = core::scheduler::Scheduler::default_path()
+        .ok_or_else(|| anyhow::anyhow!("could not determine schedule file path"))?;
+    let mut scheduler = core::scheduler::Scheduler::load(&path)?;
+
+    match action {
+        ScheduleAction::Enqueue { module, at } => {
+            let time = parse_rfc3339(&at)?;
+            let module_full = resolve_module_name(modules_by_os, &module)
+                .ok_or_else(|| anyhow::anyhow!("module '{}' not found", module))?;
+            let request_id = scheduler.insert_at(time, module_full.clone(), runner.config.test_id.clone());
+            scheduler.save(&path)?;
+            println!(
+                "{} queued {} as {} for {}",
+                "✔".bright_green(),
+                module_full,
+                request_id,
+                time.to_rfc3339()
+            );
+            Ok(None)
+        }
+
+        ScheduleAction::List => {
+            let all =
+                scheduler.list_within(core::scheduler::TimeWindow::FromTimeToEnd(DateTime::<Utc>::MIN_UTC));
+            if all.is_empty() {
+                println!("No queued entries.");
+            } else {
+                println!("{}", "Queued entries:".bright_cyan().bold());
+                for (time, entry) in all {
+                    println!(
+                        "  {} — {} ({}) at {}",
+                        entry.request_id,
+                        entry.sim_name,
+                        entry.test_id,
+                        time.to_rfc3339()
+                    );
+                }
+            }
+            Ok(None)
+        }
+
+        ScheduleAction::Delete { from, until } => {
+            let window = parse_time_window(from, until)?;
+            let removed = scheduler.delete_within(window);
+            scheduler.save(&path)?;
+            println!(
+                "Removed {} queued entr{}.",
+                removed.len(),
+                if removed.len() == 1 { "y" } else { "ies" }
+            );
+            Ok(None)
+        }
+
+        ScheduleAction::RunDue => {
+            let due = scheduler.release_due(Utc::now());
+            scheduler.save(&path)?;
+
+            if due.is_empty() {
+                println!("No entries are due yet.");
+                return Ok(None);
+            }
+
+            let mut reports = Vec::new();
+            for entry in due {
+                let sim = runner.simulations.iter().find(|s| s.name().eq_ignore_ascii_case(&entry.sim_name));
+                let entry_cfg = Config { test_id: entry.test_id.clone(), ..(*runner.config).clone() };
+                reports.push(run_module_streaming(sim.map(|s| s.as_ref()), &entry_cfg, &entry.sim_name, output));
+            }
+            Ok(Some(reports))
         }
     }
+}
 
+/// Match `name` (short or full) against every module under every OS, the
+/// same way `run_selected`/`run_scenario` resolve a single-OS module name —
+/// `schedule enqueue` takes no `--os` flag since a queued entry isn't tied
+/// to the OS the CLI happens to run under.
+fn resolve_module_name(modules_by_os: &std::collections::BTreeMap<String, Vec<String>>, name: &str) -> Option<String> {
+    modules_by_os
+        .values()
+        .flatten()
+        .find(|m| m.eq_ignore_ascii_case(name) || m.split("::").last().map(|s| s.eq_ignore_ascii_case(name)).unwrap_or(false))
+        .cloned()
+}
+
+fn parse_rfc3339(s: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(s).with_context(|| format!("'{}' is not a valid RFC3339 timestamp", s))?.with_timezone(&Utc))
+}
+
+fn parse_time_window(from: Option<String>, until: Option<String>) -> Result<core::scheduler::TimeWindow> {
+    use core::scheduler::TimeWindow;
+    match (from, until) {
+        (Some(f), Some(u)) => Ok(TimeWindow::SelectBetween(parse_rfc3339(&f)?, parse_rfc3339(&u)?)),
+        (Some(f), None) => Ok(TimeWindow::FromTimeToEnd(parse_rfc3339(&f)?)),
+        (None, Some(u)) => Ok(TimeWindow::FromStartUntil(parse_rfc3339(&u)?)),
+        (None, None) => anyhow::bail!("schedule delete requires --from and/or --until"),
+    }
+}
+
+/// `magnet init`: interactively prompt for `test_id`, the `dry_run` default,
+/// the telemetry output directory, and `report_endpoint`, validating the
+/// telemetry directory is writable and the endpoint URL parses before
+/// writing the result to `Config::config_path()` as TOML.
+fn run_init_wizard() -> Result<()> {
+    println!();
+    println!("{}", "▶ magnet init — generating config".bright_green().bold());
+    println!("{}", "Press Enter to accept the bracketed default for any prompt.".dimmed());
+    println!();
+
+    let defaults = Config::default();
+    let default_telemetry_dir =
+        core::telemetry::telemetry_dir().map(|p| p.display().to_string()).unwrap_or_default();
+
+    // A blank answer means "don't pin one down" — `test_id` is then omitted
+    // from the written file so `Config::load` keeps generating a fresh,
+    // timestamp-derived ID every run instead of every run reusing whatever
+    // ID happened to be the wizard's suggestion at the moment it was run.
+    let custom_test_id = prompt("Test ID (blank to auto-generate a fresh one every run)", "")?;
+    let dry_run = prompt_yes_no("Default to dry-run", defaults.dry_run)?;
+
+    let telemetry_dir = loop {
+        let dir = prompt("Telemetry output directory", &default_telemetry_dir)?;
+        match validate_telemetry_dir(&dir) {
+            Ok(()) => break dir,
+            Err(e) => logger::warn(&format!("'{}' is not usable as a telemetry directory: {}", dir, e)),
+        }
+    };
+
+    let report_endpoint = loop {
+        let endpoint = prompt("Report upload endpoint (blank to skip)", "")?;
+        if endpoint.is_empty() {
+            break None;
+        }
+        match reqwest::Url::parse(&endpoint) {
+            Ok(_) => break Some(endpoint),
+            Err(e) => logger::warn(&format!("'{}' is not a valid URL: {}", endpoint, e)),
+        }
+    };
+
+    let cfg = Config {
+        dry_run,
+        test_id: if custom_test_id.is_empty() { defaults.test_id.clone() } else { custom_test_id.clone() },
+        keep_artifacts: defaults.keep_artifacts,
+        beacon_profile: defaults.beacon_profile,
+        report_endpoint,
+        telemetry_dir: Some(telemetry_dir),
+    };
+
+    let path = Config::config_path().ok_or_else(|| anyhow::anyhow!("could not determine config directory"))?;
+    if let Some(parent) = path.parent() {
+        create_dir_all_logged(parent)?;
+    }
+
+    // Serialize via a `toml::Value` (rather than `cfg` directly) so the
+    // `test_id` key can be dropped when the user accepted the auto-generate
+    // default — `Config`'s `#[serde(default = "default_test_id")]` then
+    // regenerates a fresh one on every later `Config::load()`.
+    let mut value = toml::Value::try_from(&cfg).context("serializing config to TOML")?;
+    if custom_test_id.is_empty() {
+        if let Some(table) = value.as_table_mut() {
+            table.remove("test_id");
+        }
+    }
+    let toml_str = toml::to_string_pretty(&value).context("serializing config to TOML")?;
+    std::fs::write(&path, toml_str).with_context(|| format!("writing config file {}", path.display()))?;
+
+    println!();
+    println!("{} {}", "✔ wrote config to".bright_green(), path.display());
+    Ok(())
+}
+
+fn create_dir_all_logged(dir: &std::path::Path) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("creating directory {}", dir.display()))
+}
+
+/// Confirm `dir` can be created and written to, so `magnet init` doesn't
+/// silently persist a telemetry path that will fail at run time.
+fn validate_telemetry_dir(dir: &str) -> Result<()> {
+    let path = PathBuf::from(dir);
+    create_dir_all_logged(&path)?;
+
+    let marker = path.join(".magnet_init_write_test");
+    std::fs::write(&marker, b"ok").with_context(|| format!("writing to {}", path.display()))?;
+    let _ = std::fs::remove_file(&marker);
     Ok(())
 }
+
+/// Print `label` with `default` shown in brackets and read one line of
+/// input; empty input keeps the default.
+fn prompt(label: &str, default: &str) -> Result<String> {
+    print!("{} [{}]: ", label, default);
+    io::stdout().flush().ok();
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).context("reading input")?;
+
+    let trimmed = line.trim();
+    Ok(if trimmed.is_empty() { default.to_string() } else { trimmed.to_string() })
+}
+
+fn prompt_yes_no(label: &str, default: bool) -> Result<bool> {
+    let default_str = if default { "Y/n" } else { "y/N" };
+    let answer = prompt(label, default_str)?;
+    Ok(match answer.to_ascii_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    })
+}
@@ -2,8 +2,8 @@
 //! This action requires admin privileges to run.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Result};
 use chrono::Utc;
@@ -121,7 +121,28 @@ impl Simulation for AdminUserAddSimulation {
         "windows::admin_user_add"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1136.001", tactic: "Persistence", name: "Create Account: Local Account" }]
+    }
+
+    /// Creates a local Windows account — global host state, not safe to
+    /// race against other simulations under `--jobs`.
+    fn concurrency_safe(&self) -> bool {
+        false
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl AdminUserAddSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
         let username = "magnetuser";
         let password = "Magnet@1234";
@@ -139,6 +160,7 @@ impl Simulation for AdminUserAddSimulation {
                 status: "dry-run".into(),
                 details: format!("dry-run: would add '{}' to '{}'", username, group),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -156,6 +178,7 @@ impl Simulation for AdminUserAddSimulation {
                     status: "failed".into(),
                     details: e.to_string(),
                     artifact_path: None,
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 return Err(e);
@@ -183,6 +206,7 @@ impl Simulation for AdminUserAddSimulation {
         status: "telemetry".into(),
         details: serde_json::to_string(&telemetry).unwrap_or_default(),
         artifact_path: None,
+        techniques: technique_records(self.techniques()),
     }) {
         logger::warn(&format!("failed to write telemetry record: {}", e));
     }
@@ -199,6 +223,7 @@ impl Simulation for AdminUserAddSimulation {
             status: "ok".into(),
             details,
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
         let _ = write_action_record(cfg, &rec);
 
@@ -2,8 +2,8 @@
 //! telemetry + action records to the same Magnet telemetry path in Documents.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Context, Result};
 use aes_gcm::Aes256Gcm;
@@ -376,7 +376,22 @@ impl Simulation for BrowserPwdSimulation {
         "windows::browser_pwd"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1555.003", tactic: "Credential Access", name: "Credentials from Password Stores: Credentials from Web Browsers" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl BrowserPwdSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
         logger::action_running("Extracting browser saved passwords (Chrome, Edge, Firefox)");
 
@@ -390,6 +405,7 @@ impl Simulation for BrowserPwdSimulation {
                 status: "dry-run".into(),
                 details: "dry-run: no extraction performed".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -415,6 +431,7 @@ impl Simulation for BrowserPwdSimulation {
                     status: "failed".into(),
                     details: msg.clone(),
                     artifact_path: None,
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 return Err(anyhow::anyhow!(msg));
@@ -554,6 +571,7 @@ impl Simulation for BrowserPwdSimulation {
             status: "written".into(),
             details: format!("chrome_found={} edge_found={} firefox_profiles_scanned={}", chrome_found, edge_found, firefox_profiles_scanned),
             artifact_path: artifact_paths.get(0).cloned(),
+            techniques: technique_records(self.techniques()),
         };
 
         if let Err(e) = write_action_record(cfg, &rec) {
@@ -16,9 +16,9 @@ use windows::{
 };
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
+use crate::core::simulation::{Simulation, Technique};
 use crate::core::logger;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use dirs::home_dir;
 use std::fs::{create_dir_all, OpenOptions};
 
@@ -153,7 +153,22 @@ impl Simulation for CredManagerSimulation {
         "windows::cred_manager_access"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1555.004", tactic: "Credential Access", name: "Credentials from Password Stores: Windows Credential Manager" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl CredManagerSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         logger::action_running("Enumerating Windows stored credentials");
 
         let start = std::time::Instant::now();
@@ -181,10 +196,11 @@ impl Simulation for CredManagerSimulation {
                 let rec = ActionRecord {
                     test_id: cfg.test_id.clone(),
                     timestamp: Utc::now().to_rfc3339(),
-                    action: format!("T1555.004 - {}", self.name()),
+                    action: self.name().into(),
                     status: "failed".into(),
                     details: format!("CredEnumerateW error: {:?}", e),
                     artifact_path: None,
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
 
@@ -279,10 +295,11 @@ impl Simulation for CredManagerSimulation {
         let rec = ActionRecord {
             test_id: cfg.test_id.clone(),
             timestamp: Utc::now().to_rfc3339(),
-            action: format!("T1555.004 - {}", self.name()),
+            action: self.name().into(),
             status: "written".into(),
             details: format!("Enumerated {} credentials", count),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
 
         if let Err(e) = write_action_record(cfg, &rec) {
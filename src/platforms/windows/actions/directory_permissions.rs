@@ -18,8 +18,8 @@ use std::time::{Duration, Instant};
 
 use crate::core::config::Config;
 use crate::core::logger;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 
 #[derive(Default)]
 pub struct DirectoryPermissionsSim;
@@ -73,7 +73,32 @@ impl Simulation for DirectoryPermissionsSim {
         "windows::directory_permissions"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique {
+            id: "T1222.001",
+            tactic: "Defense Evasion",
+            name: "File and Directory Permissions Modification: Windows File and Directory Permissions Modification",
+        }]
+    }
+
+    /// Mutates a shared ACL on the telemetry directory — not safe to race
+    /// against other simulations under `--jobs`.
+    fn concurrency_safe(&self) -> bool {
+        false
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl DirectoryPermissionsSim {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         logger::action_running("Modifying directory permissions");
 
         let start = Instant::now();
@@ -88,6 +113,7 @@ impl Simulation for DirectoryPermissionsSim {
                 status: "dry-run".into(),
                 details: "dry-run: no ACL modified".into(),
                 artifact_path: Some(telemetry_dir.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -122,13 +148,14 @@ impl Simulation for DirectoryPermissionsSim {
         let rec = ActionRecord {
             test_id: cfg.test_id.clone(),
             timestamp: Utc::now().to_rfc3339(),
-            action: "T1222.001 - directory_permissions".into(),
+            action: "directory_permissions".into(),
             status: "written".into(),
             details: format!(
                 "Directory permissions temporarily elevated and then reverted ({} ms).",
                 elapsed.as_millis()
             ),
             artifact_path: Some(telemetry_dir.display().to_string()),
+            techniques: technique_records(self.techniques()),
         };
         let _ = write_action_record(cfg, &rec);
 
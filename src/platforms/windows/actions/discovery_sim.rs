@@ -1,5 +1,5 @@
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
+use crate::core::simulation::{Simulation, Technique};
 use anyhow::{anyhow, Context, Result};
 use chrono::Utc;
 use dirs::home_dir;
@@ -137,7 +137,22 @@ impl Simulation for DiscoverySim {
         "windows::discovery_sim"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1082", tactic: "Discovery", name: "System Information Discovery" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl DiscoverySim {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let parent = std::env::current_exe()
             .map(|p| p.display().to_string())
             .unwrap_or_else(|_| "<unknown>".to_string());
@@ -2,9 +2,9 @@
 //! MITRE: T1574.001 - Hijack Execution Flow: DLL 
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
+use crate::core::simulation::{Simulation, Technique};
 use crate::core::logger;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 
 use anyhow::Result;
 use chrono::Utc;
@@ -142,7 +142,22 @@ impl Simulation for DllLoadStormSimulation {
         MODULE_NAME
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1574.001", tactic: "Persistence", name: "Hijack Execution Flow: DLL Search Order Hijacking" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl DllLoadStormSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         logger::action_running("Launching DLL Load Storm...");
 
         if cfg.dry_run {
@@ -150,10 +165,11 @@ impl Simulation for DllLoadStormSimulation {
             let rec = ActionRecord {
                 test_id: cfg.test_id.clone(),
                 timestamp: Utc::now().to_rfc3339(),
-                action: format!("{} - {}", MITRE_TTP, MODULE_NAME),
+                action: MODULE_NAME.into(),
                 status: "dry-run".into(),
                 details: "DLL load storm skipped".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             write_action_record(cfg, &rec)?;
             logger::action_ok();
@@ -236,10 +252,11 @@ impl Simulation for DllLoadStormSimulation {
         let rec = ActionRecord {
             test_id: cfg.test_id.clone(),
             timestamp: Utc::now().to_rfc3339(),
-            action: format!("{} - {}", MITRE_TTP, MODULE_NAME),
+            action: MODULE_NAME.into(),
             status: "completed".into(),
             details: format!("{} ok, {} failed DLL loads", successful, failed),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
         let _ = write_action_record(cfg, &rec);
 
@@ -1,18 +1,24 @@
 //! Simulates EDR discovery (T1082, T1518, T1057, T1007, T1083)
+//!
+//! Process and service enumeration go through `core::winapi` first (native
+//! Win32 calls, no child process), falling back to `wmic` only when the API
+//! calls fail — `wmic` is deprecated and absent on current Windows builds.
 
 use crate::core::config::Config;
+use crate::core::exec::{Program, SpawnOptions};
 use crate::core::logger;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{write_action_record, ActionRecord, telemetry_dir};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, write_action_record, ActionRecord};
 
 use anyhow::{Context, Result};
 use chrono::Utc;
 use regex::Regex;
-use serde::Serialize;
 use std::fs;
-use std::io::Write;
-use std::process::{Command, Stdio};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// `wmic` fallback calls are bounded to this long so a hung legacy tool
+/// can't stall the EDR scan.
+const WMIC_TIMEOUT: Duration = Duration::from_secs(30);
 
 const EDR_LIST: [&str; 125] = [
     "activeconsole","ADA-PreCheck","ahnlab","amsi.dll","anti malware","anti-malware","antimalware",
@@ -42,29 +48,35 @@ const SCAN_DIRS: [&str; 3] = [
 #[derive(Default)]
 pub struct EdrDiscoverySimulation;
 
-#[derive(Serialize)]
-struct EdrTelemetry {
-    test_id: String,
-    timestamp: String,
-    detections: Vec<String>,
-    scan_dirs: Vec<String>,
-    elapsed_ms: u128,
-    parent: String,
+fn wmic_list(target: &str) -> Result<Vec<String>> {
+    let program = Program::raw("wmic", [target, "get", "name"]);
+    let opts = SpawnOptions { capture_stdout: true, capture_stderr: true, process_group: true };
+    let output = crate::core::exec::run_with_timeout(&program, &opts, WMIC_TIMEOUT).context("failed to run wmic")?;
+
+    Ok(output.stdout.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect())
 }
 
-fn wmic_list(target: &str) -> Result<Vec<String>> {
-    let output = Command::new("wmic")
-        .args([target, "get", "name"])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .context("failed to run wmic")?;
-
-    Ok(String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|l| l.trim().to_string())
-        .filter(|l| !l.is_empty())
-        .collect())
+/// Collect process and service names, preferring the native `core::winapi`
+/// backend and only shelling out to `wmic` (which is gone from current
+/// Windows builds) when the API calls themselves fail.
+#[cfg(windows)]
+fn collect_process_names() -> Result<Vec<String>> {
+    crate::core::winapi::with_fallback(crate::core::winapi::list_processes, || wmic_list("process"))
+}
+
+#[cfg(windows)]
+fn collect_service_names() -> Result<Vec<String>> {
+    crate::core::winapi::with_fallback(crate::core::winapi::list_services, || wmic_list("service"))
+}
+
+#[cfg(not(windows))]
+fn collect_process_names() -> Result<Vec<String>> {
+    wmic_list("process")
+}
+
+#[cfg(not(windows))]
+fn collect_service_names() -> Result<Vec<String>> {
+    wmic_list("service")
 }
 
 fn run_edr_scan() -> Result<Vec<String>> {
@@ -72,7 +84,7 @@ fn run_edr_scan() -> Result<Vec<String>> {
     let regex = Regex::new(&format!("(?i)({})", EDR_LIST.join("|")))?;
 
     // Processes
-    if let Ok(procs) = wmic_list("process") {
+    if let Ok(procs) = collect_process_names() {
         for p in procs {
             if regex.is_match(&p) {
                 detections.push(format!("process: {}", p));
@@ -81,7 +93,7 @@ fn run_edr_scan() -> Result<Vec<String>> {
     }
 
     // Services
-    if let Ok(svcs) = wmic_list("service") {
+    if let Ok(svcs) = collect_service_names() {
         for s in svcs {
             if regex.is_match(&s) {
                 detections.push(format!("service: {}", s));
@@ -110,12 +122,31 @@ impl Simulation for EdrDiscoverySimulation {
         "windows::edr_discovery"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[
+            Technique { id: "T1082", tactic: "Discovery", name: "System Information Discovery" },
+            Technique { id: "T1518", tactic: "Discovery", name: "Software Discovery" },
+            Technique { id: "T1057", tactic: "Discovery", name: "Process Discovery" },
+            Technique { id: "T1007", tactic: "Discovery", name: "System Service Discovery" },
+            Technique { id: "T1083", tactic: "Discovery", name: "File and Directory Discovery" },
+        ]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl EdrDiscoverySimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// `edr_discovery_<test_id>.jsonl`/`.log` without hand-rolled file I/O.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
 
-        logger::action_running(
-            "Running EDR discovery"
-        );
+        logger::action_running("Running EDR discovery");
 
         if cfg.dry_run {
             logger::info("dry-run: no discovery performed");
@@ -123,10 +154,11 @@ impl Simulation for EdrDiscoverySimulation {
             let rec = ActionRecord {
                 test_id: cfg.test_id.clone(),
                 timestamp: Utc::now().to_rfc3339(),
-                action: format!("T1082 - T1518 - T1057 - T1007 - T1083 {}", self.name()),
+                action: self.name().into(),
                 status: "dry-run".into(),
                 details: "dry-run: skipped EDR scan".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -134,55 +166,22 @@ impl Simulation for EdrDiscoverySimulation {
         }
 
         let detections = run_edr_scan()?;
+        logger::info(&format!(
+            "scanned {:?} in {}ms, {} detection(s): {:?}",
+            SCAN_DIRS,
+            start.elapsed().as_millis(),
+            detections.len(),
+            detections
+        ));
 
-        // ------------------------------------------------------
-        // TELEMETRY (PB standard)
-        // ------------------------------------------------------
-        let telem_dir = telemetry_dir()
-            .ok_or_else(|| anyhow::anyhow!("cannot determine telemetry directory"))?;
-
-        fs::create_dir_all(&telem_dir)?;
-
-        // JSONL
-        let mut jsonl = telem_dir.clone();
-        jsonl.push(format!("edr_discovery_{}.jsonl", cfg.test_id));
-        let mut jf = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&jsonl)?;
-        let telem = EdrTelemetry {
-            test_id: cfg.test_id.clone(),
-            timestamp: Utc::now().to_rfc3339(),
-            detections: detections.clone(),
-            scan_dirs: SCAN_DIRS.iter().map(|s| s.to_string()).collect(),
-            elapsed_ms: start.elapsed().as_millis(),
-            parent: std::env::current_exe()
-                .map(|p| p.display().to_string())
-                .unwrap_or("<unknown>".to_string()),
-        };
-        writeln!(jf, "{}", serde_json::to_string(&telem)?)?;
-
-        // LOG
-        let mut log = telem_dir;
-        log.push(format!("edr_discovery_{}.log", cfg.test_id));
-        let mut lf = fs::OpenOptions::new().create(true).append(true).open(&log)?;
-        writeln!(lf, "==============================================================")?;
-        writeln!(lf, "TEST ID     : {}", telem.test_id)?;
-        writeln!(lf, "TIMESTAMP   : {}", telem.timestamp)?;
-        writeln!(lf, "SCAN DIRS   : {:?}", telem.scan_dirs)?;
-        writeln!(lf, "DETECTIONS  : {:?}", telem.detections)?;
-        writeln!(lf, "ELAPSED_MS  : {}", telem.elapsed_ms)?;
-        writeln!(lf, "PARENT      : {}", telem.parent)?;
-        writeln!(lf)?;
-
-        // Action record
         let rec = ActionRecord {
             test_id: cfg.test_id.clone(),
             timestamp: Utc::now().to_rfc3339(),
-            action: format!("T1082 - T1518 - T1057 - T1007 - T1083 {}", self.name()),
+            action: self.name().into(),
             status: "completed".into(),
             details: format!("{} detections found", detections.len()),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
         let _ = write_action_record(cfg, &rec);
 
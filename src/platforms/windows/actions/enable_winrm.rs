@@ -5,8 +5,8 @@
 
 use crate::core::config::Config;
 use crate::core::logger;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{write_action_record, ActionRecord};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, write_action_record, ActionRecord};
 
 use anyhow::{Context, Result};
 use chrono::Utc;
@@ -62,7 +62,28 @@ impl Simulation for EnableWinRMSimulation {
         "windows::enable_winrm"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1021.006", tactic: "Lateral Movement", name: "Remote Services: Windows Remote Management" }]
+    }
+
+    /// Enables a Windows service and firewall rules — global host state,
+    /// not safe to race against other simulations under `--jobs`.
+    fn concurrency_safe(&self) -> bool {
+        false
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl EnableWinRMSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
 
         // -----------------------------------------------------
@@ -92,6 +113,7 @@ impl Simulation for EnableWinRMSimulation {
                 status: "dry-run".into(),
                 details: "dry-run: no commands executed".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -206,6 +228,7 @@ impl Simulation for EnableWinRMSimulation {
                 t.winrm_status, t.firewall_status, t.port_check
             ),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
 
         if let Err(e) = write_action_record(cfg, &rec) {
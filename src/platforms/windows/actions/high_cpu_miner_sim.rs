@@ -1,8 +1,8 @@
 //! Simulates a short-lived high-CPU miner workload (T1496.001).  
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Context, Result};
 use chrono::Utc;
@@ -95,7 +95,22 @@ impl Simulation for HighCpuMinerSimulation {
         "windows::high_cpu_miner_sim"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1496.001", tactic: "Impact", name: "Resource Hijacking: Compute Hijacking" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl HighCpuMinerSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
         logger::action_running(&format!("Simulating high CPU miner for {} seconds", BURN_DURATION_SECS));
 
@@ -106,10 +121,11 @@ impl Simulation for HighCpuMinerSimulation {
             let rec = ActionRecord {
                 test_id: cfg.test_id.clone(),
                 timestamp: Utc::now().to_rfc3339(),
-                action: format!("T1496.001 - {}", self.name()), 
+                action: self.name().into(),
                 status: "dry-run".into(),
                 details: format!("dry-run: no CPU load; intended duration {}s; workers {}", BURN_DURATION_SECS, default_worker_count()),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -197,10 +213,11 @@ impl Simulation for HighCpuMinerSimulation {
         let rec = ActionRecord {
             test_id: cfg.test_id.clone(),
             timestamp: Utc::now().to_rfc3339(),
-            action: format!("T1496.001 - {}", self.name()), 
+            action: self.name().into(),
             status: "written".into(),
             details: format!("CPU burn for {}s on {} workers; total iterations {}", BURN_DURATION_SECS, telemetry.worker_threads, telemetry.total_iterations),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
 
         if let Err(e) = write_action_record(cfg, &rec) {
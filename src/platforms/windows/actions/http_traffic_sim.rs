@@ -1,48 +1,206 @@
 //! Simulates a short-lived high HTTP traffic against a public domain.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Context, Result};
 use chrono::Utc;
 use dirs::home_dir;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::{create_dir_all, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use std::env;
 
-/// Number of simulated HTTP requests to perform (tuneable)
+/// Number of simulated HTTP requests to perform when a beacon profile
+/// doesn't specify `request_count`/`duration_secs` (tuneable)
 const DEFAULT_REQUEST_COUNT: usize = 100;
 
-/// Delay between requests (ms)
+/// Default delay between requests (ms), used when no beacon profile is
+/// configured.
 const REQUEST_DELAY_MS: u64 = 30;
 
-/// Target endpoint — benign public host, safe for GET/HEAD requests.
+/// Default target endpoint — benign public host, safe for GET/HEAD requests.
 const TARGET_URL: &str = "https://github.com";
 
 #[derive(Default)]
 pub struct HttpTrafficSimulation;
 
+/// One literal header to attach to every beacon request, e.g. to mimic a
+/// real agent's fixed headers (`Accept`, `X-Api-Key`, ...).
+#[derive(Debug, Clone, Deserialize)]
+struct HeaderTemplate {
+    name: String,
+    value: String,
+}
+
+/// A malleable beacon profile: how `HttpTrafficSimulation` paces and shapes
+/// its requests. Loaded from the TOML file named by `Config::beacon_profile`
+/// (env var `MAGNET_BEACON_PROFILE`); with no profile configured, `Default`
+/// reproduces the simulation's old fixed-cadence, single-target, HEAD-only
+/// behavior.
+#[derive(Debug, Clone, Deserialize)]
+struct BeaconProfile {
+    #[serde(default = "BeaconProfile::default_targets")]
+    targets: Vec<String>,
+    #[serde(default = "BeaconProfile::default_method")]
+    method: String,
+    #[serde(default = "BeaconProfile::default_user_agents")]
+    user_agents: Vec<String>,
+    #[serde(default)]
+    headers: Vec<HeaderTemplate>,
+    #[serde(default = "BeaconProfile::default_base_interval_ms")]
+    base_interval_ms: u64,
+    /// Fraction in [0.0, 1.0]; each sleep is `base_interval_ms * (1.0 +
+    /// uniform(-jitter, +jitter))`, clamped non-negative.
+    #[serde(default)]
+    jitter: f64,
+    /// Fixed request count. Takes precedence over `duration_secs` when both
+    /// are set; defaults to `DEFAULT_REQUEST_COUNT` when neither is set.
+    request_count: Option<usize>,
+    /// Run until this many seconds have elapsed instead of a fixed count.
+    duration_secs: Option<u64>,
+}
+
+impl BeaconProfile {
+    fn default_targets() -> Vec<String> {
+        vec![TARGET_URL.to_string()]
+    }
+
+    fn default_method() -> String {
+        "HEAD".to_string()
+    }
+
+    fn default_user_agents() -> Vec<String> {
+        vec![format!("MagnetHTTPTest/{}", env::consts::OS)]
+    }
+
+    fn default_base_interval_ms() -> u64 {
+        REQUEST_DELAY_MS
+    }
+
+    /// Load from `path`, falling back to the built-in defaults when no
+    /// profile is configured.
+    fn load(path: Option<&str>) -> Result<Self> {
+        let profile = match path {
+            Some(p) => {
+                let raw = std::fs::read_to_string(p)
+                    .with_context(|| format!("reading beacon profile {}", p))?;
+                toml::from_str::<Self>(&raw).with_context(|| format!("parsing beacon profile {}", p))?
+            }
+            None => Self::default(),
+        };
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// `#[serde(default = ...)]` only fills in `targets`/`user_agents` when
+    /// the key is absent from the TOML, not when it's present but empty —
+    /// reject that case explicitly instead of panicking later on a
+    /// modulo/range-by-zero.
+    fn validate(&self) -> Result<()> {
+        if self.targets.is_empty() {
+            anyhow::bail!("beacon profile has no targets");
+        }
+        if self.user_agents.is_empty() {
+            anyhow::bail!("beacon profile has no user_agents");
+        }
+        Ok(())
+    }
+
+    /// How many requests to perform when running to a fixed count rather
+    /// than a `duration_secs` budget.
+    fn request_count(&self) -> usize {
+        self.request_count.unwrap_or(DEFAULT_REQUEST_COUNT)
+    }
+
+    /// Next sleep, in ms: `base_interval_ms * (1.0 + uniform(-jitter,
+    /// +jitter))`, clamped non-negative.
+    fn jittered_interval_ms(&self) -> u64 {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = 1.0 + (fastrand::f64() * 2.0 - 1.0) * jitter;
+        ((self.base_interval_ms as f64) * factor).max(0.0) as u64
+    }
+}
+
+impl Default for BeaconProfile {
+    fn default() -> Self {
+        Self {
+            targets: Self::default_targets(),
+            method: Self::default_method(),
+            user_agents: Self::default_user_agents(),
+            headers: Vec::new(),
+            base_interval_ms: Self::default_base_interval_ms(),
+            jitter: 0.0,
+            request_count: Some(DEFAULT_REQUEST_COUNT),
+            duration_secs: None,
+        }
+    }
+}
+
+/// Min/max/mean/stdev of the realized (jittered) inter-request sleeps, in ms.
+#[derive(Debug, Default, Serialize)]
+struct IntervalStats {
+    min_ms: u64,
+    max_ms: u64,
+    mean_ms: f64,
+    stdev_ms: f64,
+}
+
+fn compute_interval_stats(intervals: &[u64]) -> IntervalStats {
+    if intervals.is_empty() {
+        return IntervalStats::default();
+    }
+    let min_ms = *intervals.iter().min().unwrap();
+    let max_ms = *intervals.iter().max().unwrap();
+    let mean_ms = intervals.iter().sum::<u64>() as f64 / intervals.len() as f64;
+    let variance = intervals
+        .iter()
+        .map(|&v| {
+            let d = v as f64 - mean_ms;
+            d * d
+        })
+        .sum::<f64>()
+        / intervals.len() as f64;
+    IntervalStats { min_ms, max_ms, mean_ms, stdev_ms: variance.sqrt() }
+}
+
 #[derive(Serialize)]
 struct HttpTrafficTelemetry {
     test_id: String,
     timestamp: String,
-    target_url: String,
+    method: String,
+    targets: Vec<String>,
+    user_agents: Vec<String>,
+    base_interval_ms: u64,
+    jitter: f64,
     requests_attempted: usize,
     requests_succeeded: usize,
     avg_latency_ms: f64,
-    user_agent: String,
+    interval_stats: IntervalStats,
+    per_target_counts: BTreeMap<String, usize>,
     elapsed_ms: u128,
     parent: String,
 }
 
+/// Results of running a beacon profile to completion.
+struct BeaconRunResult {
+    attempted: usize,
+    succeeded: usize,
+    avg_latency_ms: f64,
+    interval_stats: IntervalStats,
+    per_target_counts: BTreeMap<String, usize>,
+}
+
 /// A realistic simulation of HTTP beacon / exfil / C2 traffic patterns.
-/// This module performs safe HTTPS HEAD requests to `https://github.com`,
-/// with randomized headers and pacing to emulate beacon-like traffic.
-/// It never sends any sensitive data — payloads are synthetic and constant.
+/// With no beacon profile configured, this performs safe HTTPS HEAD requests
+/// to `https://github.com` with randomized headers and fixed pacing; a
+/// profile can reshape this into multi-target, jittered, method-varied
+/// beaconing. It never sends any sensitive data — payloads are synthetic and
+/// constant.
 impl HttpTrafficSimulation {
     fn telemetry_dir() -> Option<PathBuf> {
         home_dir().map(|mut p| {
@@ -81,64 +239,114 @@ impl HttpTrafficSimulation {
         writeln!(lf, "================================================================")?;
         writeln!(lf, "TEST ID   : {}", rec.test_id)?;
         writeln!(lf, "TIMESTAMP : {}", rec.timestamp)?;
-        writeln!(lf, "TARGET URL: {}", rec.target_url)?;
+        writeln!(lf, "METHOD    : {}", rec.method)?;
+        writeln!(lf, "TARGETS   : {}", rec.targets.join(", "))?;
         writeln!(lf, "REQUESTS  : attempted={}, succeeded={}", rec.requests_attempted, rec.requests_succeeded)?;
         writeln!(lf, "AVG_LAT_MS: {:.2}", rec.avg_latency_ms)?;
-        writeln!(lf, "USERAGENT : {}", rec.user_agent)?;
+        writeln!(lf, "INTERVAL_MS: min={}, max={}, mean={:.2}, stdev={:.2}",
+            rec.interval_stats.min_ms, rec.interval_stats.max_ms, rec.interval_stats.mean_ms, rec.interval_stats.stdev_ms)?;
+        writeln!(lf, "PER_TARGET: {:?}", rec.per_target_counts)?;
         writeln!(lf, "PARENT    : {}", rec.parent)?;
         writeln!(lf, "ELAPSED_MS: {}", rec.elapsed_ms)?;
         writeln!(lf)?;
         Ok(())
     }
 
-    /// Perform a series of safe HEAD requests to TARGET_URL to simulate HTTP beacons.
-    fn perform_requests(n: usize) -> (usize, f64) {
+    /// Perform a series of beacon requests per `profile`: targets rotate
+    /// round-robin, User-Agents are sampled from the pool per request, and
+    /// the sleep between requests is jittered around `base_interval_ms`.
+    fn perform_requests(profile: &BeaconProfile) -> Result<BeaconRunResult> {
         let client = reqwest::blocking::Client::builder()
             .timeout(Duration::from_secs(5))
             .build()
             .expect("failed to build HTTP client");
 
-        let mut success = 0usize;
+        let method = profile
+            .method
+            .parse::<reqwest::Method>()
+            .with_context(|| format!("invalid HTTP method in beacon profile: {}", profile.method))?;
+
+        let mut succeeded = 0usize;
         let mut total_latency: f64 = 0.0;
-        let ua_base = format!("MagnetHTTPTest/{}", env::consts::OS);
+        let mut attempted = 0usize;
+        let mut intervals: Vec<u64> = Vec::new();
+        let mut per_target_counts: BTreeMap<String, usize> = BTreeMap::new();
 
-        for i in 0..n {
-            let start = Instant::now();
-            let user_agent = format!("{} (iteration:{:03})", ua_base, i + 1);
+        // `request_count` takes precedence over `duration_secs` when both are
+        // set; falling back to a fixed count when a profile somehow sets
+        // neither (the built-in default always sets `request_count`).
+        let budget_count = match (profile.request_count, profile.duration_secs) {
+            (Some(n), _) => Some(n),
+            (None, Some(_)) => None,
+            (None, None) => Some(profile.request_count()),
+        };
+        let deadline = profile.duration_secs.map(|secs| Instant::now() + Duration::from_secs(secs));
+
+        // Exactly one of `budget_count`/`deadline` is always `Some`.
+        let mut i = 0usize;
+        loop {
+            if let Some(n) = budget_count {
+                if i >= n {
+                    break;
+                }
+            } else if let Some(deadline) = deadline {
+                if Instant::now() >= deadline {
+                    break;
+                }
+            }
 
-            let res = client
-                .head(TARGET_URL)
-                .header("User-Agent", &user_agent)
+            let target = &profile.targets[i % profile.targets.len()];
+            let user_agent = &profile.user_agents[fastrand::usize(0..profile.user_agents.len())];
+
+            let start = Instant::now();
+            let mut req = client
+                .request(method.clone(), target)
+                .header("User-Agent", user_agent.as_str())
                 .header("X-Magnet-Test", "purple-simulation")
-                .header("X-Magnet-Seq", format!("{}", i + 1))
-                .send();
+                .header("X-Magnet-Seq", format!("{}", i + 1));
+            for h in &profile.headers {
+                req = req.header(h.name.as_str(), h.value.as_str());
+            }
+            let res = req.send();
 
             let elapsed_ms = start.elapsed().as_millis() as f64;
             total_latency += elapsed_ms;
+            attempted += 1;
+            *per_target_counts.entry(target.clone()).or_insert(0) += 1;
 
             match res {
                 Ok(r) if r.status().is_success() => {
-                    success += 1;
-                    logger::info(&format!("HTTP {} {}ms", r.status(), elapsed_ms));
+                    succeeded += 1;
+                    logger::info(&format!("HTTP {} {}ms ({})", r.status(), elapsed_ms, target));
                 }
                 Ok(r) => {
-                    logger::warn(&format!("HTTP non-success status {} after {}ms", r.status(), elapsed_ms));
+                    logger::warn(&format!("HTTP non-success status {} after {}ms ({})", r.status(), elapsed_ms, target));
                 }
                 Err(e) => {
-                    logger::warn(&format!("HTTP request failed: {}", e));
+                    logger::warn(&format!("HTTP request failed: {} ({})", e, target));
                 }
             }
 
-            std::thread::sleep(Duration::from_millis(REQUEST_DELAY_MS));
+            let sleep_ms = profile.jittered_interval_ms();
+            intervals.push(sleep_ms);
+            std::thread::sleep(Duration::from_millis(sleep_ms));
+
+            i += 1;
         }
 
-        let avg_latency = if n > 0 {
-            total_latency / n as f64
+        let avg_latency_ms = if attempted > 0 {
+            total_latency / attempted as f64
         } else {
             0.0
         };
 
-        (success, avg_latency)
+        Ok(BeaconRunResult {
+            attempted,
+            succeeded,
+            avg_latency_ms,
+            interval_stats: compute_interval_stats(&intervals),
+            per_target_counts,
+        })
     }
 }
 
@@ -147,13 +355,41 @@ impl Simulation for HttpTrafficSimulation {
         "windows::http_traffic_sim"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1071.001", tactic: "Command and Control", name: "Application Layer Protocol: Web Protocols" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl HttpTrafficSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
-        logger::action_running("Simulating HTTP beaconing / exfil traffic to https://github.com");
+
+        let profile = BeaconProfile::load(cfg.beacon_profile.as_deref())?;
+
+        logger::action_running(&format!(
+            "Simulating HTTP beaconing / exfil traffic to {}",
+            profile.targets.join(", ")
+        ));
 
         // Dry-run: no network calls, only telemetry
         if cfg.dry_run {
-            logger::info("dry-run: would perform HTTP HEAD requests to https://github.com");
+            logger::info(&format!(
+                "dry-run: would perform {} {} requests to {} (base_interval_ms={}, jitter={})",
+                profile.method,
+                profile.request_count(),
+                profile.targets.join(", "),
+                profile.base_interval_ms,
+                profile.jitter
+            ));
             let rec = ActionRecord {
                 test_id: cfg.test_id.clone(),
                 timestamp: Utc::now().to_rfc3339(),
@@ -161,6 +397,7 @@ impl Simulation for HttpTrafficSimulation {
                 status: "dry-run".into(),
                 details: "dry-run: no network requests made".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -168,7 +405,7 @@ impl Simulation for HttpTrafficSimulation {
         }
 
         // Execute the simulated traffic
-        let (succeeded, avg_latency) = Self::perform_requests(DEFAULT_REQUEST_COUNT);
+        let result = Self::perform_requests(&profile)?;
         let elapsed = start.elapsed();
 
         let parent = std::env::current_exe()
@@ -178,11 +415,16 @@ impl Simulation for HttpTrafficSimulation {
         let t = HttpTrafficTelemetry {
             test_id: cfg.test_id.clone(),
             timestamp: Utc::now().to_rfc3339(),
-            target_url: TARGET_URL.to_string(),
-            requests_attempted: DEFAULT_REQUEST_COUNT,
-            requests_succeeded: succeeded,
-            avg_latency_ms: avg_latency,
-            user_agent: format!("MagnetHTTPTest/{}", env::consts::OS),
+            method: profile.method.clone(),
+            targets: profile.targets.clone(),
+            user_agents: profile.user_agents.clone(),
+            base_interval_ms: profile.base_interval_ms,
+            jitter: profile.jitter,
+            requests_attempted: result.attempted,
+            requests_succeeded: result.succeeded,
+            avg_latency_ms: result.avg_latency_ms,
+            interval_stats: result.interval_stats,
+            per_target_counts: result.per_target_counts,
             elapsed_ms: elapsed.as_millis(),
             parent,
         };
@@ -197,9 +439,15 @@ impl Simulation for HttpTrafficSimulation {
             timestamp: Utc::now().to_rfc3339(),
             action: "http_traffic_sim".into(),
             status: "written".into(),
-            details: format!("Performed {} HTTP requests to {} ({} successes, avg {:.2}ms)",
-                             DEFAULT_REQUEST_COUNT, TARGET_URL, succeeded, avg_latency),
+            details: format!(
+                "Performed {} HTTP requests to {} ({} successes, avg {:.2}ms)",
+                t.requests_attempted,
+                t.targets.join(", "),
+                t.requests_succeeded,
+                t.avg_latency_ms
+            ),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
         if let Err(e) = write_action_record(cfg, &rec) {
             logger::warn(&format!("failed to write action record: {}", e));
@@ -209,3 +457,63 @@ impl Simulation for HttpTrafficSimulation {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_interval_stats_empty_is_default() {
+        let stats = compute_interval_stats(&[]);
+        assert_eq!(stats.min_ms, 0);
+        assert_eq!(stats.max_ms, 0);
+        assert_eq!(stats.mean_ms, 0.0);
+        assert_eq!(stats.stdev_ms, 0.0);
+    }
+
+    #[test]
+    fn compute_interval_stats_single_value_has_zero_stdev() {
+        let stats = compute_interval_stats(&[100]);
+        assert_eq!(stats.min_ms, 100);
+        assert_eq!(stats.max_ms, 100);
+        assert_eq!(stats.mean_ms, 100.0);
+        assert_eq!(stats.stdev_ms, 0.0);
+    }
+
+    #[test]
+    fn compute_interval_stats_min_max_mean() {
+        let stats = compute_interval_stats(&[10, 20, 30, 40]);
+        assert_eq!(stats.min_ms, 10);
+        assert_eq!(stats.max_ms, 40);
+        assert_eq!(stats.mean_ms, 25.0);
+        assert!(stats.stdev_ms > 0.0);
+    }
+
+    #[test]
+    fn jittered_interval_ms_zero_jitter_is_exact() {
+        let profile = BeaconProfile { jitter: 0.0, base_interval_ms: 50, ..BeaconProfile::default() };
+        for _ in 0..20 {
+            assert_eq!(profile.jittered_interval_ms(), 50);
+        }
+    }
+
+    #[test]
+    fn jittered_interval_ms_stays_within_bounds() {
+        let profile = BeaconProfile { jitter: 0.5, base_interval_ms: 100, ..BeaconProfile::default() };
+        for _ in 0..200 {
+            let ms = profile.jittered_interval_ms();
+            assert!(ms <= 150, "{} exceeded the +50% jitter bound", ms);
+            assert!(ms >= 50, "{} fell below the -50% jitter bound", ms);
+        }
+    }
+
+    #[test]
+    fn jittered_interval_ms_clamps_out_of_range_jitter() {
+        // `jitter` isn't validated elsewhere, so a profile with jitter > 1.0
+        // must still clamp rather than ever go negative.
+        let profile = BeaconProfile { jitter: 5.0, base_interval_ms: 10, ..BeaconProfile::default() };
+        for _ in 0..50 {
+            assert!(profile.jittered_interval_ms() <= 10, "clamp should cap jitter at 1.0");
+        }
+    }
+}
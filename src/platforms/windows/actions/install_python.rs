@@ -2,8 +2,8 @@
 //! Needs admin rights for full automation.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 
 use anyhow::{Context, Result};
@@ -140,7 +140,28 @@ impl Simulation for InstallPythonSimulation {
         "windows::install_python"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1072", tactic: "Execution", name: "Software Deployment Tools" }]
+    }
+
+    /// Installs software system-wide via winget — global host state, not
+    /// safe to race against other simulations under `--jobs`.
+    fn concurrency_safe(&self) -> bool {
+        false
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl InstallPythonSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = std::time::Instant::now();
 
         logger::action_running("Installing Python via winget");
@@ -159,6 +180,7 @@ impl Simulation for InstallPythonSimulation {
                 status: "dry-run".into(),
                 details: "dry-run: no settings or installation run".into(),
                 artifact_path: Some(path.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -176,6 +198,7 @@ impl Simulation for InstallPythonSimulation {
                 status: "failed".into(),
                 details: format!("settings.json error: {}", e),
                 artifact_path: Some(path.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             return Err(e);
@@ -194,6 +217,7 @@ impl Simulation for InstallPythonSimulation {
                     status: "failed".into(),
                     details: format!("winget error: {}", e),
                     artifact_path: Some(path.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 return Err(e);
@@ -226,6 +250,7 @@ impl Simulation for InstallPythonSimulation {
             status: "written".into(),
             details: result,
             artifact_path: Some(path.display().to_string()),
+            techniques: technique_records(self.techniques()),
         };
         if let Err(e) = write_action_record(cfg, &rec) {
             logger::warn(&format!("failed to write action record: {}", e));
@@ -18,8 +18,8 @@ use std::thread::sleep;
 
 use crate::core::config::Config;
 use crate::core::logger;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 
 /// Resolve %USERPROFILE%\Documents\MagnetTelemetry\keylogger_sim_<test_id>.log
 fn output_path(cfg: &Config) -> Option<PathBuf> {
@@ -179,7 +179,22 @@ impl Simulation for KeyloggerSim {
         "windows::keylogger_sim"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1056.001", tactic: "Collection", name: "Input Capture: Keylogging" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl KeyloggerSim {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         logger::action_running("Running keylogger simulation (10s)");
 
         let start = Instant::now();
@@ -195,6 +210,7 @@ impl Simulation for KeyloggerSim {
                 status: "dry-run".into(),
                 details: "dry-run: keylogger logic skipped".into(),
                 artifact_path: Some(out.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -227,6 +243,7 @@ impl Simulation for KeyloggerSim {
                         elapsed.as_millis()
                     ),
                     artifact_path: Some(out.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 logger::action_ok();
@@ -241,6 +258,7 @@ impl Simulation for KeyloggerSim {
                     status: "failed".into(),
                     details: format!("error: {}", e),
                     artifact_path: Some(out.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 Err(e)
@@ -2,8 +2,8 @@
 //! then port-scan that host.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record, telemetry_dir};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record, telemetry_dir};
 use crate::core::logger;
 
 use anyhow::{Context, Result};
@@ -72,7 +72,22 @@ impl Simulation for NetworkPortScanSimulation {
         "windows::network_port_scan"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1046", tactic: "Discovery", name: "Network Service Discovery" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl NetworkPortScanSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
 
         // Create Tokio runtime for async scanning
         let rt = tokio::runtime::Runtime::new().context("creating tokio runtime")?;
@@ -99,6 +114,7 @@ impl Simulation for NetworkPortScanSimulation {
                         status: "failed".into(),
                         details: "No local IPv4 detected".into(),
                         artifact_path: None,
+                        techniques: technique_records(self.techniques()),
                     };
                     let _ = write_action_record(cfg, &rec);
                     return Err(anyhow::anyhow!("no local IPv4"));
@@ -121,6 +137,7 @@ impl Simulation for NetworkPortScanSimulation {
                     status: "dry-run".into(),
                     details: "dry-run: local-ip-only".into(),
                     artifact_path: None,
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
 
@@ -159,6 +176,7 @@ impl Simulation for NetworkPortScanSimulation {
                         status: "failed".into(),
                         details: "no alive hosts detected".into(),
                         artifact_path: None,
+                        techniques: technique_records(self.techniques()),
                     };
                     let _ = write_action_record(cfg, &rec);
                     return Err(anyhow::anyhow!("no alive hosts"));
@@ -245,6 +263,7 @@ impl Simulation for NetworkPortScanSimulation {
                     local, host, open_ports
                 ),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
 
@@ -1,8 +1,8 @@
 //! Simulates rapid creation of multiple top-level overlapped windows via the Win32 API.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::Result;
 use chrono::Utc;
@@ -25,7 +25,22 @@ impl Simulation for OpenManyWindowsSimulation {
         "windows::open_many_windows"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1529", tactic: "Impact", name: "System Shutdown/Reboot" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl OpenManyWindowsSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         logger::action_running("Opening 250 GUI windows and closing them (Windows API test)");
 
         if cfg.dry_run {
@@ -37,6 +52,7 @@ impl Simulation for OpenManyWindowsSimulation {
                 status: "dry-run".into(),
                 details: "dry-run: no actual windows created".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -130,6 +146,7 @@ impl Simulation for OpenManyWindowsSimulation {
             status: "completed".into(),
             details: format!("Opened and closed 250 windows in {} ms", elapsed),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
         let _ = write_action_record(cfg, &rec);
 
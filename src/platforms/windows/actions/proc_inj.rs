@@ -1,7 +1,7 @@
 //! Executes an embedded (safe) test payload via in-memory execution to validate process-injection detection.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
+use crate::core::simulation::{Simulation, Technique};
 use crate::core::logger;
 use anyhow::{anyhow, Context, Result};
 use chrono::Utc;
@@ -246,7 +246,22 @@ impl Simulation for ProcInjSim {
         "windows::proc_inj_sim"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1055", tactic: "Defense Evasion", name: "Process Injection" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl ProcInjSim {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         self.execute_shellcode(cfg)?;
         Ok(())
     }
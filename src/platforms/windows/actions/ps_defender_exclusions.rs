@@ -1,100 +1,93 @@
 //! Adds Windows Defender exclusion paths via PowerShell for testing and simulation.
 //! This action requires admin privileges to run.
 
+use crate::core::cleanup::CleanupGuard;
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::exec::{Program, SpawnOptions};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Context, Result};
 use chrono::Utc;
 use std::env;
-use std::fs::{create_dir_all, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
-use std::process::Command;
+use std::time::Duration;
+
+/// Defender queries and mutations are bounded to this long so a stalled
+/// `powershell.exe` can't hang a run indefinitely.
+const POWERSHELL_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Adds Windows Defender exclusions for specific user folders and logs telemetry.
 #[derive(Default)]
 pub struct PsDefenderExclusions;
 
 impl PsDefenderExclusions {
+    /// Read the current Defender exclusion paths, preferring the native
+    /// `core::winapi` registry read and only shelling out to
+    /// `powershell.exe` if that fails.
     fn retrieve_defender_path_exclusions() -> Result<String> {
-        let output = Command::new("powershell.exe")
-            .args([
-                "-NoProfile",
-                "-NonInteractive",
-                "-Command",
-                "Get-MpPreference | Select -ExpandProperty ExclusionPath",
-            ])
-            .output()
-            .context("Failed to run PowerShell to get Defender exclusions")?;
+        #[cfg(windows)]
+        {
+            let list = crate::core::winapi::with_fallback(crate::core::winapi::defender_exclusion_paths, || {
+                Self::retrieve_defender_path_exclusions_via_powershell()
+                    .map(|raw| raw.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect())
+            })?;
+            return Ok(list.join("\n"));
+        }
 
-        let list = String::from_utf8_lossy(&output.stdout).to_string();
-        Ok(list)
+        #[cfg(not(windows))]
+        Self::retrieve_defender_path_exclusions_via_powershell()
     }
 
-    fn add_exclusions() -> Result<()> {
+    fn retrieve_defender_path_exclusions_via_powershell() -> Result<String> {
+        let program = Program::powershell("Get-MpPreference | Select -ExpandProperty ExclusionPath");
+        let opts = SpawnOptions { capture_stdout: true, capture_stderr: true, process_group: true };
+        let output = crate::core::exec::run_with_timeout(&program, &opts, POWERSHELL_TIMEOUT)
+            .context("Failed to run PowerShell to get Defender exclusions")?;
+        Ok(output.stdout)
+    }
+
+    /// Adds the exclusions and returns the paths that were requested, so the
+    /// caller can register a matching `CleanupGuard` undo action.
+    fn add_exclusions() -> Result<Vec<String>> {
         let userprofile = env::var("USERPROFILE").unwrap_or_else(|_| "C:\\".into());
         let desktop = format!("{}\\Desktop\\Magnet", userprofile);
         let documents = format!("{}\\Documents\\Magnet", userprofile);
         let downloads = format!("{}\\Downloads\\Magnet", userprofile);
+        let paths = vec![desktop, documents, downloads];
 
-        let ps_script = format!(
-            "Add-MpPreference -ExclusionPath '{}','{}','{}'",
-            desktop, documents, downloads
-        );
-
-        let status = Command::new("powershell.exe")
-            .args([
-                "-NoProfile",
-                "-NonInteractive",
-                "-ExecutionPolicy",
-                "Bypass",
-                "-Command",
-                &ps_script,
-            ])
-            .status()
+        let ps_script = format!("Add-MpPreference -ExclusionPath {}", Self::quoted_path_list(&paths));
+        let program = Program::powershell(ps_script);
+        let opts = SpawnOptions { capture_stdout: true, capture_stderr: true, process_group: true };
+        let output = crate::core::exec::run_with_timeout(&program, &opts, POWERSHELL_TIMEOUT)
             .context("Failed to execute PowerShell command")?;
 
-        if status.success() {
-            logger::info("✅ Defender exclusions added successfully.");
-        } else {
-            logger::warn("❌ Failed to add exclusions. Run as Administrator.");
+        if !output.success() {
+            return Err(anyhow::anyhow!("Add-MpPreference exited with failure status: {:?}", output.end));
         }
 
-        Ok(())
+        logger::info("✅ Defender exclusions added successfully.");
+        Ok(paths)
     }
 
-    fn telemetry_dir() -> Option<PathBuf> {
-        dirs::home_dir().map(|mut p| {
-            p.push("Documents");
-            p.push("MagnetTelemetry");
-            p
-        })
+    /// Removes previously added exclusion paths. Used as the `CleanupGuard`
+    /// undo action so a run always restores baseline Defender config.
+    fn remove_exclusions(paths: &[String]) -> Result<()> {
+        let ps_script = format!("Remove-MpPreference -ExclusionPath {}", Self::quoted_path_list(paths));
+        let program = Program::powershell(ps_script);
+        let opts = SpawnOptions { capture_stdout: true, capture_stderr: true, process_group: true };
+        let output = crate::core::exec::run_with_timeout(&program, &opts, POWERSHELL_TIMEOUT)
+            .context("Failed to execute PowerShell command to remove Defender exclusions")?;
+
+        if !output.success() {
+            return Err(anyhow::anyhow!("Remove-MpPreference exited with failure status: {:?}", output.end));
+        }
+        Ok(())
     }
 
-    fn write_detailed_telemetry(cfg: &Config, details: &str) -> Result<()> {
-        let dir = Self::telemetry_dir()
-            .ok_or_else(|| anyhow::anyhow!("could not determine telemetry output path"))?;
-        create_dir_all(&dir)
-            .with_context(|| format!("creating telemetry directory {}", dir.display()))?;
-
-        let mut log = dir.clone();
-        log.push(format!("ps_defender_exclusions_{}.log", cfg.test_id));
-        let mut lf = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&log)
-            .with_context(|| format!("opening telemetry log {}", log.display()))?;
-
-        writeln!(lf, "============================================================")?;
-        writeln!(lf, "TEST ID   : {}", cfg.test_id)?;
-        writeln!(lf, "TIMESTAMP : {}", Utc::now().to_rfc3339())?;
-        writeln!(lf, "DETAILS   : {}", details)?;
-        writeln!(lf)?;
-
-        Ok(())
+    fn quoted_path_list(paths: &[String]) -> String {
+        paths.iter().map(|p| format!("'{}'", p)).collect::<Vec<_>>().join(",")
     }
+
 }
 
 impl Simulation for PsDefenderExclusions {
@@ -102,7 +95,28 @@ impl Simulation for PsDefenderExclusions {
         "windows::ps_defender_exclusions"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1562.001", tactic: "Defense Evasion", name: "Impair Defenses: Disable or Modify Tools" }]
+    }
+
+    /// Mutates shared Windows Defender exclusion settings — not safe to
+    /// race against other simulations under `--jobs`.
+    fn concurrency_safe(&self) -> bool {
+        false
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl PsDefenderExclusions {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so
+    /// `logger::info!`/`warn!` calls below are attributed to
+    /// `ps_defender_exclusions_<test_id>.jsonl`/`.log` automatically.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         logger::action_running("Adding Windows Defender exclusions via PowerShell");
 
         if cfg.dry_run {
@@ -114,26 +128,38 @@ impl Simulation for PsDefenderExclusions {
                 status: "dry-run".into(),
                 details: "dry-run: no PowerShell executed".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
             return Ok(());
         }
 
+        let mut cleanup = CleanupGuard::new(self.name(), cfg);
+
         // 1) Add exclusions
-        if let Err(e) = Self::add_exclusions() {
-            logger::action_fail("failed to add Defender exclusions");
-            let rec = ActionRecord {
-                test_id: cfg.test_id.clone(),
-                timestamp: Utc::now().to_rfc3339(),
-                action: "ps_defender_exclusions".into(),
-                status: "failed".into(),
-                details: format!("add_exclusions error: {}", e),
-                artifact_path: None,
-            };
-            let _ = write_action_record(cfg, &rec);
-            return Err(e);
-        }
+        let added_paths = match Self::add_exclusions() {
+            Ok(paths) => paths,
+            Err(e) => {
+                logger::action_fail("failed to add Defender exclusions");
+                let rec = ActionRecord {
+                    test_id: cfg.test_id.clone(),
+                    timestamp: Utc::now().to_rfc3339(),
+                    action: "ps_defender_exclusions".into(),
+                    status: "failed".into(),
+                    details: format!("add_exclusions error: {}", e),
+                    artifact_path: None,
+                    techniques: technique_records(self.techniques()),
+                };
+                let _ = write_action_record(cfg, &rec);
+                return Err(e);
+            }
+        };
+
+        cleanup.push(format!("Remove-MpPreference -ExclusionPath {}", added_paths.join(",")), {
+            let paths = added_paths.clone();
+            move || Self::remove_exclusions(&paths)
+        });
 
         // 2) Retrieve and log exclusions
         let exclusions = match Self::retrieve_defender_path_exclusions() {
@@ -144,10 +170,7 @@ impl Simulation for PsDefenderExclusions {
             }
         };
 
-        // Write telemetry
-        if let Err(e) = Self::write_detailed_telemetry(cfg, &exclusions) {
-            logger::warn(&format!("failed to write detailed telemetry: {}", e));
-        }
+        logger::info(&format!("current Defender exclusions: {}", exclusions));
 
         let rec = ActionRecord {
             test_id: cfg.test_id.clone(),
@@ -156,6 +179,7 @@ impl Simulation for PsDefenderExclusions {
             status: "written".into(),
             details: "Successfully added Defender exclusions".into(),
             artifact_path: None,
+            techniques: technique_records(self.techniques()),
         };
         if let Err(e) = write_action_record(cfg, &rec) {
             logger::warn(&format!("failed to write action record: {}", e));
@@ -1,6 +1,6 @@
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Context, Result};
 use chrono::Utc;
@@ -135,7 +135,22 @@ impl Simulation for PsElevWhoami {
         "windows::ps_elev_whoami"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1033", tactic: "Discovery", name: "System Owner/User Discovery" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl PsElevWhoami {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
         logger::action_running(
             "Simulating: open PowerShell (attempt elevated), enable script execution, run whoami",
@@ -160,6 +175,7 @@ impl Simulation for PsElevWhoami {
                     example_output
                 ),
                 artifact_path: Some(example_output),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -281,6 +297,7 @@ impl Simulation for PsElevWhoami {
                 telemetry.elevated_start_status
             ),
             artifact_path: Some(elevated_output_path.display().to_string()),
+            techniques: technique_records(self.techniques()),
         };
 
         if let Err(e) = write_action_record(cfg, &rec) {
@@ -1,6 +1,6 @@
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use anyhow::{Context, Result};
 use chrono::Utc;
 use dirs::desktop_dir;
@@ -46,7 +46,22 @@ impl Simulation for RansomNote {
         "windows::ransom_note"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1491.001", tactic: "Impact", name: "Defacement: Internal Defacement" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl RansomNote {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let test_id = &cfg.test_id;
         let content = Self::build_note_content(test_id);
 
@@ -66,6 +81,7 @@ impl Simulation for RansomNote {
                 status: "dry-run".into(),
                 details: "dry-run: no file written".into(),
                 artifact_path: Some(path.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             return Ok(());
@@ -93,6 +109,7 @@ impl Simulation for RansomNote {
             status: "written".into(),
             details: format!("Wrote ransom note to Desktop: {}", path.display()),
             artifact_path: Some(path.display().to_string()),
+            techniques: technique_records(self.techniques()),
         };
 
         if let Err(e) = write_action_record(cfg, &rec) {
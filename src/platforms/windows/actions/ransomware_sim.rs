@@ -1,8 +1,8 @@
 //! Simulates realistic ransomware behavior for purple-team testing.
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Context, Result};
 use chrono::Utc;
@@ -65,8 +65,12 @@ impl RansomSimulation {
         desktop_dir()
     }
 
-    fn note_path(desktop: &Path) -> PathBuf {
-        desktop.join("RANSOM_NOTE.txt")
+    /// Distinct from `windows::ransom_note`'s `RANSOM_NOTE.txt` — both sims
+    /// default to `concurrency_safe() == true`, so sharing one filename let
+    /// `--jobs` schedule them onto separate threads truncating each other's
+    /// note mid-write.
+    fn note_path(desktop: &Path, test_id: &str) -> PathBuf {
+        desktop.join(format!("RANSOM_NOTE_{}.txt", test_id))
     }
 
     /// Telemetry dir: %USERPROFILE%\Documents\MagnetTelemetry
@@ -214,14 +218,29 @@ impl Simulation for RansomSimulation {
         "windows::ransomware_sim"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1486", tactic: "Impact", name: "Data Encrypted for Impact" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl RansomSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
 
         let test_id = &cfg.test_id;
         let note_content = Self::build_note_content(test_id);
 
         let desktop = Self::desktop_path().context("could not determine Desktop path")?;
-        let note_path = Self::note_path(&desktop);
+        let note_path = Self::note_path(&desktop, test_id);
 
         logger::action_running("Simulating ransomware: create repo, encrypt files, oldest sc deletion, drop note");
 
@@ -235,6 +254,7 @@ impl Simulation for RansomSimulation {
                 status: "dry-run".into(),
                 details: "dry-run: no repo created, no files encrypted".into(),
                 artifact_path: Some(note_path.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -254,6 +274,7 @@ impl Simulation for RansomSimulation {
                     status: "failed".into(),
                     details: format!("create files error: {}", e),
                     artifact_path: Some(repo.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 return Err(e);
@@ -301,6 +322,7 @@ impl Simulation for RansomSimulation {
                     status: "failed".into(),
                     details: format!("note write error: {}", e),
                     artifact_path: Some(note_path.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 return Err(e);
@@ -336,6 +358,7 @@ impl Simulation for RansomSimulation {
             status: "written".into(),
             details: format!("Repo: {} created; {} files encrypted", repo.display(), encrypted),
             artifact_path: Some(note_path.display().to_string()),
+            techniques: technique_records(self.techniques()),
         };
         if let Err(e) = write_action_record(cfg, &rec) {
             logger::warn(&format!("failed to write action record: {}", e));
@@ -6,8 +6,8 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use chrono::Utc;
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 
 use std::path::PathBuf;
@@ -117,7 +117,22 @@ impl Simulation for RecordMicSim {
         "windows::record_mic"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1123", tactic: "Collection", name: "Audio Capture" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl RecordMicSim {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
 
         logger::action_running("Recording microphone input (10 seconds)");
@@ -134,6 +149,7 @@ impl Simulation for RecordMicSim {
                 status: "dry-run".into(),
                 details: "dry-run: microphone not accessed".into(),
                 artifact_path: Some(out.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -155,6 +171,7 @@ impl Simulation for RecordMicSim {
                         elapsed.as_millis()
                     ),
                     artifact_path: Some(out.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
 
@@ -171,6 +188,7 @@ impl Simulation for RecordMicSim {
                     status: "failed".into(),
                     details: format!("error: {}", e),
                     artifact_path: Some(out.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
 
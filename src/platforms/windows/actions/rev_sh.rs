@@ -20,8 +20,8 @@ use tokio::net::{TcpListener, TcpStream};
 
 use crate::core::config::Config;
 use crate::core::logger;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 
 #[derive(Default)]
 pub struct RevSh;
@@ -189,7 +189,22 @@ impl Simulation for RevSh {
         "windows::rev_sh"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1095", tactic: "Command and Control", name: "Non-Application Layer Protocol" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl RevSh {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let out_path = Self::output_path()
             .ok_or_else(|| anyhow::anyhow!("could not resolve MagnetTelemetry path"))?;
 
@@ -202,6 +217,7 @@ impl Simulation for RevSh {
                 status: "dry-run".into(),
                 details: "dry-run: no listener started".into(),
                 artifact_path: Some(out_path.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             return Ok(());
@@ -231,6 +247,7 @@ impl Simulation for RevSh {
             status: status.into(),
             details,
             artifact_path: Some(out_path.display().to_string()),
+            techniques: technique_records(self.techniques()),
         };
 
         let _ = write_action_record(cfg, &rec);
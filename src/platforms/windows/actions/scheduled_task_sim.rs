@@ -1,16 +1,22 @@
 //! Creation and execution of a benign Windows scheduled task.
 
+use crate::core::cleanup::CleanupGuard;
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::exec::{Program, SpawnOptions};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 use anyhow::{Context, Result};
 use chrono::{Local, Utc, Duration};
 use dirs::home_dir;
-use std::fs::{create_dir_all, OpenOptions, File};
+use std::fs::{create_dir_all, File};
 use std::io::Write;
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::time::Duration as StdDuration;
+
+/// `schtasks.exe` invocations are bounded to this long so a stuck call can't
+/// hang the simulation.
+const SCHTASKS_TIMEOUT: StdDuration = StdDuration::from_secs(30);
 
 /// Create a benign Windows Scheduled Task to simulate persistence activity.
 /// The task runs a short PowerShell script writing a marker file in Documents\MagnetTelemetry.
@@ -61,67 +67,36 @@ impl ScheduledTaskSim {
         );
 
         // schtasks /Create /SC ONCE /TN <task> /TR "<action>" /ST HH:mm /F
-        let output = Command::new("schtasks.exe")
-            .args([
-                "/Create",
-                "/SC", "ONCE",
-                "/TN", task_name,
-                "/TR", &action,
-                "/ST", start_time_hhmm,
-                "/F",
-            ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        let program = Program::raw(
+            "schtasks.exe",
+            ["/Create", "/SC", "ONCE", "/TN", task_name, "/TR", action.as_str(), "/ST", start_time_hhmm, "/F"],
+        );
+        let opts = SpawnOptions { capture_stdout: true, capture_stderr: true, process_group: true };
+        let output = crate::core::exec::run_with_timeout(&program, &opts, SCHTASKS_TIMEOUT)
             .context("failed to spawn schtasks.exe")?;
 
-        if output.status.success() {
+        if output.success() {
             logger::info(&format!("Scheduled task {} created.", task_name));
             Ok(())
         } else {
-            Err(anyhow::anyhow!(
-                "schtasks create failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ))
+            Err(anyhow::anyhow!("schtasks create failed ({:?}): {}", output.end, output.stderr))
         }
     }
 
     fn delete_schtask(task_name: &str) -> Result<()> {
-        let output = Command::new("schtasks.exe")
-            .args(["/Delete", "/TN", task_name, "/F"])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        let program = Program::raw("schtasks.exe", ["/Delete", "/TN", task_name, "/F"]);
+        let opts = SpawnOptions { capture_stdout: true, capture_stderr: true, process_group: true };
+        let output = crate::core::exec::run_with_timeout(&program, &opts, SCHTASKS_TIMEOUT)
             .context("failed to spawn schtasks delete")?;
 
-        if output.status.success() {
+        if output.success() {
             logger::info(&format!("Scheduled task {} deleted.", task_name));
             Ok(())
         } else {
-            Err(anyhow::anyhow!(
-                "schtasks delete failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ))
+            Err(anyhow::anyhow!("schtasks delete failed ({:?}): {}", output.end, output.stderr))
         }
     }
 
-    fn write_detailed_telemetry(cfg: &Config, details: &str) -> Result<()> {
-        let dir = Self::telemetry_dir()
-            .ok_or_else(|| anyhow::anyhow!("could not determine telemetry output path"))?;
-        create_dir_all(&dir)?;
-
-        let mut log = dir.clone();
-        log.push(format!("scheduled_task_{}.log", cfg.test_id));
-        let mut lf = OpenOptions::new().create(true).append(true).open(&log)?;
-
-        writeln!(lf, "================================================================")?;
-        writeln!(lf, "TEST ID   : {}", cfg.test_id)?;
-        writeln!(lf, "TIMESTAMP : {}", Utc::now().to_rfc3339())?;
-        writeln!(lf, "DETAILS   : {}", details)?;
-        writeln!(lf)?;
-
-        Ok(())
-    }
 }
 
 impl Simulation for ScheduledTaskSim {
@@ -129,7 +104,28 @@ impl Simulation for ScheduledTaskSim {
         "windows::scheduled_task_sim"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1053.005", tactic: "Persistence", name: "Scheduled Task/Job: Scheduled Task" }]
+    }
+
+    /// Registers a scheduled task in Task Scheduler — global host state,
+    /// not safe to race against other simulations under `--jobs`.
+    fn concurrency_safe(&self) -> bool {
+        false
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl ScheduledTaskSim {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so
+    /// `logger::info!`/`warn!` calls below are attributed to
+    /// `scheduled_task_sim_<test_id>.jsonl`/`.log` automatically.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         logger::action_running("Creating benign Scheduled Task");
         logger::action_running("Waiting 1 minute for task execution");
 
@@ -142,6 +138,7 @@ impl Simulation for ScheduledTaskSim {
                 status: "dry-run".into(),
                 details: "dry-run: no scheduled task created".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -164,7 +161,8 @@ impl Simulation for ScheduledTaskSim {
         let start_time_hhmm = start_time.format("%H:%M").to_string();
 
         // Create the scheduled task
-        if let Err(e) = Self::create_schtask(&Self::task_name(cfg), &ps_script, &start_time_hhmm) {
+        let task_name = Self::task_name(cfg);
+        if let Err(e) = Self::create_schtask(&task_name, &ps_script, &start_time_hhmm) {
             logger::action_fail("failed to create scheduled task");
             let rec = ActionRecord {
                 test_id: cfg.test_id.clone(),
@@ -173,13 +171,43 @@ impl Simulation for ScheduledTaskSim {
                 status: "failed".into(),
                 details: format!("create task error: {}", e),
                 artifact_path: Some(telemetry_dir.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             return Err(e);
         }
 
-        // Wait briefly for execution
-        std::thread::sleep(std::time::Duration::from_secs(61));
+        // Register the rollback before doing anything else that could panic
+        // or bail out early, so the task is never left behind on the host.
+        let mut cleanup = CleanupGuard::new(self.name(), cfg);
+        cleanup.push(format!("schtasks /Delete /TN {}", task_name), {
+            let task_name = task_name.clone();
+            move || Self::delete_schtask(&task_name)
+        });
+
+        // Queue the artifact check against the scheduler instead of a flat
+        // `sleep(61s)`, so the wait is expressed as a release-time entry that
+        // persists to disk and would survive this process restarting.
+        let schedule_path = crate::core::scheduler::Scheduler::default_path();
+        let mut scheduler = match &schedule_path {
+            Some(p) => crate::core::scheduler::Scheduler::load(p).unwrap_or_default(),
+            None => crate::core::scheduler::Scheduler::new(),
+        };
+        let request_id = scheduler.insert_at(Utc::now() + Duration::seconds(61), self.name(), cfg.test_id.clone());
+        if let Some(p) = &schedule_path {
+            let _ = scheduler.save(p);
+        }
+        logger::info(&format!("queued artifact check {} for release in ~61s", request_id));
+
+        loop {
+            if !scheduler.release_due(Utc::now()).is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_secs(2));
+        }
+        if let Some(p) = &schedule_path {
+            let _ = scheduler.save(p);
+        }
 
         // Check if artifact written
         let details = match std::fs::read_to_string(&artifact) {
@@ -193,14 +221,9 @@ impl Simulation for ScheduledTaskSim {
             }
         };
 
-        // Cleanup
-        if let Err(e) = Self::delete_schtask(&Self::task_name(cfg)) {
-            logger::warn(&format!("Failed to delete scheduled task: {}", e));
-        }
-
-        if let Err(e) = Self::write_detailed_telemetry(cfg, &details) {
-            logger::warn(&format!("failed to write detailed telemetry: {}", e));
-        }
+        // Cleanup now happens automatically when `cleanup` drops at the end
+        // of this function, whether we reach here normally or bail early.
+        logger::info(&details);
 
         let rec = ActionRecord {
             test_id: cfg.test_id.clone(),
@@ -209,6 +232,7 @@ impl Simulation for ScheduledTaskSim {
             status: "written".into(),
             details: format!("Scheduled task executed; {}", details.lines().next().unwrap_or("")),
             artifact_path: Some(telemetry_dir.display().to_string()),
+            techniques: technique_records(self.techniques()),
         };
         if let Err(e) = write_action_record(cfg, &rec) {
             logger::warn(&format!("failed to write action record: {}", e));
@@ -1,6 +1,6 @@
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
-use crate::core::telemetry::{ActionRecord, write_action_record};
+use crate::core::simulation::{Simulation, Technique};
+use crate::core::telemetry::{technique_records, ActionRecord, write_action_record};
 use crate::core::logger;
 
 use anyhow::{Context, Result};
@@ -137,7 +137,22 @@ impl Simulation for ScreenshotSimulation {
         "windows::screenshot"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1113", tactic: "Collection", name: "Screen Capture" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl ScreenshotSimulation {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         let start = Instant::now();
 
         logger::action_running("Capturing screenshot ");
@@ -163,6 +178,7 @@ impl Simulation for ScreenshotSimulation {
                 status: "dry-run".into(),
                 details: "dry-run: screenshot not captured".into(),
                 artifact_path: Some(shot_path.display().to_string()),
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -195,6 +211,7 @@ impl Simulation for ScreenshotSimulation {
                     status: "written".into(),
                     details: "screenshot capture completed".into(),
                     artifact_path: Some(shot_path.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
 
@@ -210,6 +227,7 @@ impl Simulation for ScreenshotSimulation {
                     status: "failed".into(),
                     details: format!("capture error: {}", e),
                     artifact_path: Some(shot_path.display().to_string()),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 Err(e)
@@ -1,9 +1,9 @@
 //! Reetrieves wifi credentials stored in windows.  
 
 use crate::core::config::Config;
-use crate::core::simulation::Simulation;
+use crate::core::simulation::{Simulation, Technique};
 use crate::core::logger;
-use crate::core::telemetry::{write_action_record, ActionRecord};
+use crate::core::telemetry::{technique_records, write_action_record, ActionRecord};
 use anyhow::{anyhow, Context, Result};
 use chrono::Utc;
 use regex::Regex;
@@ -182,7 +182,22 @@ impl Simulation for WifiCreds {
         "windows::wifi_creds"
     }
 
+    /// ATT&CK techniques this simulation exercises; see
+    /// `core::registry` for the enumerable mapping over all sims.
+    fn techniques(&self) -> &'static [Technique] {
+        &[Technique { id: "T1552.001", tactic: "Credential Access", name: "Unsecured Credentials: Credentials In Files" }]
+    }
+
     fn run(&self, cfg: &Config) -> Result<()> {
+        crate::core::telemetry::scoped(self.name(), &cfg.test_id, || self.run_scoped(cfg))
+    }
+}
+
+impl WifiCreds {
+    /// Body of `run`, executed inside a `telemetry::scoped` context so every
+    /// `logger::info!`/`warn!` and `write_action_record` call below lands in
+    /// its own `<sim>_<test_id>.jsonl`/`.log` instead of the shared `unknown` fallback.
+    fn run_scoped(&self, cfg: &Config) -> Result<()> {
         // Minimal console output only (no passwords)
         logger::action_running("Enumerating Wi-Fi profiles (passwords go to telemetry)");
 
@@ -195,6 +210,7 @@ impl Simulation for WifiCreds {
                 status: "dry-run".into(),
                 details: "dry-run: no profiles extracted".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -213,6 +229,7 @@ impl Simulation for WifiCreds {
                     status: "failed".into(),
                     details: format!("list error: {}", e),
                     artifact_path: None,
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &rec);
                 return Err(e);
@@ -229,6 +246,7 @@ impl Simulation for WifiCreds {
                 status: "no-profiles".into(),
                 details: "no Wi-Fi profiles detected on this host".into(),
                 artifact_path: None,
+                techniques: technique_records(self.techniques()),
             };
             let _ = write_action_record(cfg, &rec);
             logger::action_ok();
@@ -283,6 +301,7 @@ impl Simulation for WifiCreds {
                     status: "written".into(),
                     details: format!("Wrote {} profiles to wifi_credentials_{}.jsonl", record.entries.len(), cfg.test_id),
                     artifact_path: Some(format!("wifi_credentials_{}.jsonl", cfg.test_id)),
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &act);
 
@@ -298,6 +317,7 @@ impl Simulation for WifiCreds {
                     status: "failed".into(),
                     details: format!("telemetry error: {}", e),
                     artifact_path: None,
+                    techniques: technique_records(self.techniques()),
                 };
                 let _ = write_action_record(cfg, &act);
                 Err(e)
@@ -6,8 +6,9 @@ use magnet::platforms::windows::actions::ransomware_sim::RansomSimulation;
 use dirs::desktop_dir;
 
 /// This test runs the RansomNote simulation and verifies that
-/// a file named "RANSOM_NOTE.txt" (or "MAGNET_RANSOM_NOTE.txt")
-/// exists on the Desktop after execution.
+/// a file named "RANSOM_NOTE_<test_id>.txt" exists on the Desktop after
+/// execution — the filename is test_id-scoped so it can't collide with
+/// `windows::ransom_note`'s `RANSOM_NOTE.txt` when both run under `--jobs`.
 ///
 /// ⚠️  Run this test only on authorized systems where you can
 /// safely create and delete a benign file on the Desktop.
@@ -17,13 +18,13 @@ fn test_ransom_note_creates_file_on_desktop() {
     let desktop = desktop_dir().expect("Could not determine Desktop path");
 
     // 2. Compute expected file path
-    let note_path = desktop.join("RANSOM_NOTE.txt");
+    let cfg = Config::default();
+    let note_path = desktop.join(format!("RANSOM_NOTE_{}.txt", cfg.test_id));
 
     // 3. Clean up any leftover file from prior runs
     let _ = fs::remove_file(&note_path);
 
     // 4. Run the simulation (writes the file)
-    let cfg = Config::default();
     let ransom = RansomSimulation::default();
     ransom.run(&cfg).expect("RansomNote simulation failed");
 
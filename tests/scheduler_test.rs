@@ -0,0 +1,78 @@
+use chrono::{Duration, Utc};
+
+use magnet::core::scheduler::{Scheduler, TimeWindow};
+
+/// `release_due` must only pop entries whose release time has arrived, in
+/// release-time order, and leave later entries queued.
+#[test]
+fn release_due_pops_only_entries_at_or_before_now() {
+    let now = Utc::now();
+    let mut sched = Scheduler::new();
+
+    sched.insert_at(now - Duration::minutes(5), "windows::discovery_sim", "test-1");
+    let later_id = sched.insert_at(now + Duration::minutes(5), "windows::discovery_sim", "test-1");
+
+    let due = sched.release_due(now);
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].sim_name, "windows::discovery_sim");
+    assert!(!sched.is_empty());
+
+    // The future entry is untouched by the earlier release.
+    let window = TimeWindow::FromTimeToEnd(now);
+    let remaining = sched.list_within(window);
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].1.request_id, later_id);
+}
+
+#[test]
+fn release_due_orders_by_release_time() {
+    let now = Utc::now();
+    let mut sched = Scheduler::new();
+
+    sched.insert_at(now - Duration::minutes(1), "windows::b", "test-1");
+    sched.insert_at(now - Duration::minutes(10), "windows::a", "test-1");
+
+    let due = sched.release_due(now);
+    assert_eq!(due.len(), 2);
+    assert_eq!(due[0].sim_name, "windows::a");
+    assert_eq!(due[1].sim_name, "windows::b");
+}
+
+/// `TimeWindow` variants are exercised indirectly through `list_within` /
+/// `delete_within`, since `TimeWindow::contains` itself is private.
+#[test]
+fn time_window_variants_bound_correctly() {
+    let base = Utc::now();
+    let mut sched = Scheduler::new();
+
+    sched.insert_at(base - Duration::hours(1), "windows::early", "test-1");
+    sched.insert_at(base, "windows::mid", "test-1");
+    sched.insert_at(base + Duration::hours(1), "windows::late", "test-1");
+
+    let before_mid = sched.list_within(TimeWindow::FromStartUntil(base));
+    assert_eq!(before_mid.len(), 2);
+
+    let from_mid = sched.list_within(TimeWindow::FromTimeToEnd(base));
+    assert_eq!(from_mid.len(), 2);
+
+    let between = sched.list_within(TimeWindow::SelectBetween(base - Duration::minutes(1), base + Duration::minutes(1)));
+    assert_eq!(between.len(), 1);
+    assert_eq!(between[0].1.sim_name, "windows::mid");
+}
+
+#[test]
+fn delete_within_removes_only_matching_entries() {
+    let base = Utc::now();
+    let mut sched = Scheduler::new();
+
+    sched.insert_at(base - Duration::hours(1), "windows::early", "test-1");
+    sched.insert_at(base + Duration::hours(1), "windows::late", "test-1");
+
+    let deleted = sched.delete_within(TimeWindow::FromStartUntil(base));
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].sim_name, "windows::early");
+
+    let remaining = sched.list_within(TimeWindow::SelectBetween(base - Duration::hours(2), base + Duration::hours(2)));
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].1.sim_name, "windows::late");
+}